@@ -0,0 +1,228 @@
+use crate::shell::init::Shell;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Pick and run scripts from package.json")]
+pub struct Cli {
+    /// Script to run straight away instead of picking one
+    pub script: Option<String>,
+
+    /// Rerun the last script run in this project, with the same args (`scriptor !!` also works)
+    #[arg(long)]
+    pub last: bool,
+
+    /// Print the graph of which scripts call which in DOT format and exit
+    #[arg(long)]
+    pub graph: bool,
+
+    /// Rerun the picked script whenever a file matching GLOB changes (can be repeated)
+    #[arg(long, value_name = "GLOB")]
+    pub watch: Vec<String>,
+
+    /// Print the command for the picked script instead of running it
+    #[arg(long, conflicts_with = "watch")]
+    pub print: bool,
+
+    /// Set an environment variable for the run (can be repeated)
+    #[arg(long = "env", value_name = "KEY=VALUE", value_parser = parse_assignment)]
+    pub env: Vec<(String, String)>,
+
+    /// Load `.env` and `.env.MODE` into the environment of the run
+    #[arg(long, value_name = "MODE")]
+    pub env_mode: Option<String>,
+
+    /// Save the run's output to the project's run logs, which the picker can browse
+    #[arg(long, conflicts_with_all = ["watch", "print"])]
+    pub log: bool,
+
+    /// Use the project in DIR instead of the current directory
+    #[arg(long, value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Print the project's script names for shell completion and exit
+    #[arg(long, hide = true)]
+    pub complete: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Extra arguments passed through to the script
+    #[arg(last = true)]
+    pub args: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print shell integration binding alt-s to insert a picked script's command at the prompt,
+    /// e.g. `eval "$(scriptor init bash)"`
+    Init { shell: Shell },
+    /// Print completions for the shell, e.g. `scriptor completions zsh > _scriptor`
+    Completions { shell: clap_complete::Shell },
+    /// List the project's scripts
+    List {
+        /// Print the scripts as JSON, in the format described in the README
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run a script straight away, including ones named like a subcommand, e.g. `scriptor run list`
+    Run {
+        script: String,
+
+        /// Extra arguments passed through to the script
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+}
+
+impl Cli {
+    pub fn parse_args() -> Self {
+        Self::parse_from(normalise_args(std::env::args()))
+    }
+
+    /// The script to run straight away and its extra arguments, whether named directly or
+    /// through `run`.
+    pub fn direct_script(&mut self) -> (Option<String>, Vec<String>) {
+        match self.command.take() {
+            Some(Command::Run { script, args }) => (Some(script), args),
+            command => {
+                self.command = command;
+                (self.script.take(), std::mem::take(&mut self.args))
+            }
+        }
+    }
+}
+
+fn parse_assignment(assignment: &str) -> Result<(String, String), String> {
+    let (key, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got `{assignment}`"))?;
+
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Rewrites the shell-style `!!` shorthand into `--last` before clap sees it.
+fn normalise_args(args: impl Iterator<Item = String>) -> Vec<String> {
+    let mut passthrough = false;
+
+    args.map(|arg| {
+        if arg == "--" {
+            passthrough = true;
+        }
+
+        if arg == "!!" && !passthrough {
+            "--last".to_string()
+        } else {
+            arg
+        }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::parse_from(normalise_args(args.iter().map(|arg| arg.to_string())))
+    }
+
+    #[test]
+    fn it_bang_bang_is_treated_as_last() {
+        let cli = parse(&["scriptor", "!!"]);
+
+        assert!(cli.last);
+    }
+
+    #[test]
+    fn it_watch_can_be_repeated() {
+        let cli = parse(&["scriptor", "--watch", "src/**/*.ts", "--watch", "*.json"]);
+
+        assert_eq!(cli.watch, vec!["src/**/*.ts", "*.json"]);
+    }
+
+    #[test]
+    fn it_parses_init_subcommand() {
+        let cli = parse(&["scriptor", "init", "zsh"]);
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::Init { shell: Shell::Zsh })
+        ));
+    }
+
+    #[test]
+    fn it_parses_script_and_subcommands_apart() {
+        let cli = parse(&["scriptor", "build", "--", "--watch"]);
+        assert_eq!(cli.script, Some("build".to_string()));
+        assert_eq!(cli.args, vec!["--watch"]);
+
+        let cli = parse(&["scriptor", "completions", "fish"]);
+        assert_eq!(cli.script, None);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Completions {
+                shell: clap_complete::Shell::Fish
+            })
+        ));
+    }
+
+    #[test]
+    fn it_run_subcommand_runs_scripts_named_like_subcommands() {
+        let mut cli = parse(&["scriptor", "run", "list", "--", "--json"]);
+        assert_eq!(
+            cli.direct_script(),
+            (Some("list".to_string()), vec!["--json".to_string()])
+        );
+
+        let mut cli = parse(&["scriptor", "build", "--", "--watch"]);
+        assert_eq!(
+            cli.direct_script(),
+            (Some("build".to_string()), vec!["--watch".to_string()])
+        );
+    }
+
+    #[test]
+    fn it_cwd_takes_a_directory() {
+        let cli = parse(&["scriptor", "--cwd", "packages/app", "build"]);
+
+        assert_eq!(cli.cwd, Some(PathBuf::from("packages/app")));
+        assert_eq!(cli.script.as_deref(), Some("build"));
+    }
+
+    #[test]
+    fn it_env_takes_assignments() {
+        let cli = parse(&["scriptor", "--env", "DEBUG=1", "--env", "URL=a=b", "test"]);
+
+        assert_eq!(
+            cli.env,
+            vec![
+                ("DEBUG".to_string(), "1".to_string()),
+                ("URL".to_string(), "a=b".to_string())
+            ]
+        );
+        assert!(Cli::try_parse_from(["scriptor", "--env", "DEBUG"]).is_err());
+    }
+
+    #[test]
+    fn it_print_conflicts_with_watch() {
+        let result = Cli::try_parse_from(["scriptor", "--print", "--watch", "*.ts"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn it_log_conflicts_with_watch_and_print() {
+        assert!(parse(&["scriptor", "--log", "test"]).log);
+        assert!(Cli::try_parse_from(["scriptor", "--log", "--watch", "*.ts"]).is_err());
+        assert!(Cli::try_parse_from(["scriptor", "--log", "--print"]).is_err());
+    }
+
+    #[test]
+    fn it_args_after_separator_are_passed_through() {
+        let cli = parse(&["scriptor", "--", "--watch", "!!"]);
+
+        assert!(!cli.last);
+        assert_eq!(cli.args, vec!["--watch", "!!"]);
+    }
+}
@@ -0,0 +1,442 @@
+use crate::runner::{env::RunEnv, package_manager::PackageManager};
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::{Regex, RegexSet};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const PROJECT_CONFIG: &str = ".scriptor.toml";
+
+/// Keywords of scripts that confirm before running when `confirm_scripts` is not set.
+pub const DEFAULT_CONFIRM_SCRIPTS: [&str; 3] = [
+    r"(?i)\b(deploy|publish|release|destroy|drop|reset|purge|wipe|nuke)\b",
+    r"\brm\s+-[a-zA-Z]*[rf]",
+    r"(^|\s)--force\b",
+];
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Name,
+    Recent,
+}
+
+/// How a script matching `confirm_scripts` is confirmed.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmWith {
+    /// Pressing y.
+    #[default]
+    Yes,
+    /// Typing the script's name.
+    Name,
+}
+
+/// Settings read from `~/.config/scriptor/config.toml` and the project's `.scriptor.toml`,
+/// where anything set in the project file overrides the global one.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    package_manager: Option<PackageManager>,
+    hidden_scripts: Option<Vec<String>>,
+    /// A preset name, or the path to a theme file. Paths in a config file are relative to the
+    /// directory it's in.
+    theme: Option<String>,
+    sort: Option<SortOrder>,
+    vim_mode: Option<bool>,
+    /// Regexes for scripts that must be confirmed before they run, matched against each script's
+    /// name and command.
+    confirm_scripts: Option<Vec<String>>,
+    confirm_with: Option<ConfirmWith>,
+    /// Load `.env` into the environment of every run.
+    dotenv: Option<bool>,
+    /// Also load `.env.<mode>`, which implies `dotenv`.
+    env_mode: Option<String>,
+    /// Save the output of every run to the project's run logs.
+    log_runs: Option<bool>,
+    #[serde(default)]
+    keys: HashMap<String, String>,
+    #[serde(skip)]
+    hidden_matcher: GlobSet,
+    #[serde(skip)]
+    confirm_matcher: RegexSet,
+}
+
+impl Default for Config {
+    /// An empty config, compiled like an empty config file so it has the default confirm
+    /// patterns.
+    fn default() -> Self {
+        Self {
+            package_manager: None,
+            hidden_scripts: None,
+            theme: None,
+            sort: None,
+            vim_mode: None,
+            confirm_scripts: None,
+            confirm_with: None,
+            dotenv: None,
+            env_mode: None,
+            log_runs: None,
+            keys: HashMap::new(),
+            hidden_matcher: GlobSet::empty(),
+            confirm_matcher: RegexSet::empty(),
+        }
+        .compile()
+        .expect("the default confirm patterns are valid")
+    }
+}
+
+impl Config {
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let global = match global_config_path() {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        let project = Self::from_file(&project_dir.join(PROJECT_CONFIG))?;
+
+        global.merge(project)
+    }
+
+    /// Reads a config file, treating a missing file as empty.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Could not read config file {}", path.display()))?;
+
+        let mut config = Self::parse(&contents)
+            .wrap_err_with(|| format!("Invalid config file {}", path.display()))?;
+        config.theme = config
+            .theme
+            .map(|theme| resolve_theme_path(theme, path.parent().unwrap_or(Path::new(""))));
+
+        Ok(config)
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let config: Self = toml::from_str(contents).map_err(|error| eyre!("{error}"))?;
+
+        config.compile()
+    }
+
+    /// Layers `project` over `self`, with project settings taking precedence.
+    pub fn merge(self, project: Self) -> Result<Self> {
+        let mut keys = self.keys;
+        keys.extend(project.keys);
+
+        Self {
+            package_manager: project.package_manager.or(self.package_manager),
+            hidden_scripts: project.hidden_scripts.or(self.hidden_scripts),
+            theme: project.theme.or(self.theme),
+            sort: project.sort.or(self.sort),
+            vim_mode: project.vim_mode.or(self.vim_mode),
+            confirm_scripts: project.confirm_scripts.or(self.confirm_scripts),
+            confirm_with: project.confirm_with.or(self.confirm_with),
+            dotenv: project.dotenv.or(self.dotenv),
+            env_mode: project.env_mode.or(self.env_mode),
+            log_runs: project.log_runs.or(self.log_runs),
+            keys,
+            hidden_matcher: GlobSet::empty(),
+            confirm_matcher: RegexSet::empty(),
+        }
+        .compile()
+    }
+
+    fn compile(mut self) -> Result<Self> {
+        let mut hidden = GlobSetBuilder::new();
+
+        for pattern in self.hidden_scripts.iter().flatten() {
+            let glob = Glob::new(pattern)
+                .wrap_err_with(|| format!("Invalid pattern `{pattern}` in hidden_scripts"))?;
+            hidden.add(glob);
+        }
+
+        self.hidden_matcher = hidden.build()?;
+
+        let confirm: Vec<&str> = match &self.confirm_scripts {
+            Some(patterns) => patterns.iter().map(String::as_str).collect(),
+            None => DEFAULT_CONFIRM_SCRIPTS.to_vec(),
+        };
+
+        for pattern in &confirm {
+            Regex::new(pattern)
+                .wrap_err_with(|| format!("Invalid pattern `{pattern}` in confirm_scripts"))?;
+        }
+
+        self.confirm_matcher = RegexSet::new(confirm)?;
+
+        Ok(self)
+    }
+
+    pub fn theme(&self) -> Option<&str> {
+        self.theme.as_deref()
+    }
+
+    pub fn sort(&self) -> SortOrder {
+        self.sort.unwrap_or_default()
+    }
+
+    pub fn vim_mode(&self) -> bool {
+        self.vim_mode.unwrap_or(false)
+    }
+
+    pub fn confirm_with(&self) -> ConfirmWith {
+        self.confirm_with.unwrap_or_default()
+    }
+
+    pub fn log_runs(&self) -> bool {
+        self.log_runs.unwrap_or(false)
+    }
+
+    /// Key bindings by action name, as written in the `[keys]` table.
+    pub fn keys(&self) -> &HashMap<String, String> {
+        &self.keys
+    }
+
+    pub fn is_hidden(&self, script: &str) -> bool {
+        self.hidden_matcher.is_match(script)
+    }
+
+    /// Whether the script called `name`, which runs `command`, has to be confirmed before it runs.
+    pub fn needs_confirmation(&self, name: &str, command: &str) -> bool {
+        self.confirm_matcher.is_match(name) || self.confirm_matcher.is_match(command)
+    }
+
+    /// The env files every run loads, with `mode` taking the place of `env_mode` if given.
+    pub fn run_env(&self, mode: Option<&str>) -> RunEnv {
+        let mode = mode.or(self.env_mode.as_deref());
+
+        if self.dotenv.unwrap_or(false) || mode.is_some() {
+            RunEnv::dotenv(mode)
+        } else {
+            RunEnv::default()
+        }
+    }
+
+    pub fn package_manager(&self, project_dir: &Path) -> PackageManager {
+        self.package_manager
+            .unwrap_or_else(|| PackageManager::detect(project_dir))
+    }
+}
+
+/// Makes a theme file path absolute, expanding `~` and resolving relative paths against `dir`.
+/// Preset names are left as they are.
+fn resolve_theme_path(theme: String, dir: &Path) -> String {
+    if !theme.ends_with(".toml") {
+        return theme;
+    }
+
+    let path = match theme.strip_prefix("~/").zip(dirs::home_dir()) {
+        Some((rest, home)) => home.join(rest),
+        None => dir.join(&theme),
+    };
+
+    path.to_string_lossy().into_owned()
+}
+
+/// `$XDG_CONFIG_HOME/scriptor/config.toml`, falling back to `~/.config` on every platform.
+fn global_config_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+
+    Some(config_dir.join("scriptor").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    #[test]
+    fn it_parse_reads_every_setting() {
+        let config = Config::parse(
+            r#"
+            package_manager = "pnpm"
+            hidden_scripts = ["pre*", "postinstall"]
+            theme = "light"
+            sort = "recent"
+            vim_mode = true
+            log_runs = true
+
+            [keys]
+            run = "ctrl-r"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.package_manager, Some(PackageManager::Pnpm));
+        assert_eq!(config.theme, Some("light".to_string()));
+        assert_eq!(config.sort, Some(SortOrder::Recent));
+        assert_eq!(config.vim_mode, Some(true));
+        assert_eq!(config.log_runs, Some(true));
+        assert_eq!(config.keys["run"], "ctrl-r");
+        assert!(config.is_hidden("prebuild"));
+        assert!(config.is_hidden("postinstall"));
+        assert!(!config.is_hidden("build"));
+    }
+
+    #[test]
+    fn it_merge_prefers_project_settings() {
+        let global = Config::parse(
+            r#"
+            package_manager = "npm"
+            theme = "dark"
+            [keys]
+            run = "enter"
+            cancel = "esc"
+            "#,
+        )
+        .unwrap();
+        let project = Config::parse(
+            r#"
+            package_manager = "bun"
+            [keys]
+            run = "ctrl-r"
+            "#,
+        )
+        .unwrap();
+
+        let config = global.merge(project).unwrap();
+
+        assert_eq!(config.package_manager, Some(PackageManager::Bun));
+        assert_eq!(config.theme, Some("dark".to_string()));
+        assert_eq!(config.keys["run"], "ctrl-r");
+        assert_eq!(config.keys["cancel"], "esc");
+    }
+
+    #[test]
+    fn it_parse_rejects_unknown_settings() {
+        let error = Config::parse("colour = \"red\"").unwrap_err();
+
+        assert!(error.to_string().contains("unknown field `colour`"));
+    }
+
+    #[test]
+    fn it_parse_rejects_invalid_values() {
+        let error = Config::parse("package_manager = \"maven\"").unwrap_err();
+
+        assert!(error.to_string().contains("unknown variant `maven`"));
+    }
+
+    #[test]
+    fn it_parse_rejects_invalid_hidden_patterns() {
+        let error = Config::parse("hidden_scripts = [\"build:[\"]").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid pattern `build:[` in hidden_scripts"
+        );
+    }
+
+    #[test]
+    fn it_needs_confirmation_for_risky_scripts_by_default() {
+        for config in [Config::parse("").unwrap(), Config::default()] {
+            assert!(config.needs_confirmation("deploy:prod", "vercel --prod"));
+            assert!(config.needs_confirmation("db:reset", "prisma migrate reset"));
+            assert!(config.needs_confirmation("clean", "rm -rf dist"));
+            assert!(config.needs_confirmation("Publish", "lerna publish"));
+            assert!(!config.needs_confirmation("build", "tsc"));
+            assert!(!config.needs_confirmation("preset", "node preset.js"));
+        }
+    }
+
+    #[test]
+    fn it_needs_confirmation_uses_configured_patterns() {
+        let config = Config::parse(
+            r#"
+            confirm_scripts = ["^seed", "terraform apply"]
+            confirm_with = "name"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.needs_confirmation("seed:dev", "node seed.js"));
+        assert!(config.needs_confirmation("infra", "terraform apply -auto-approve"));
+        assert!(!config.needs_confirmation("deploy", "vercel"));
+        assert_eq!(config.confirm_with, Some(ConfirmWith::Name));
+    }
+
+    #[test]
+    fn it_run_env_loads_dotenv_files_when_enabled() {
+        assert_eq!(Config::parse("").unwrap().run_env(None), RunEnv::default());
+        assert_eq!(
+            Config::parse("dotenv = true").unwrap().run_env(None),
+            RunEnv::dotenv(None)
+        );
+
+        let config = Config::parse(r#"env_mode = "test""#).unwrap();
+
+        assert_eq!(config.run_env(None), RunEnv::dotenv(Some("test")));
+        assert_eq!(config.run_env(Some("ci")), RunEnv::dotenv(Some("ci")));
+    }
+
+    #[test]
+    fn it_parse_rejects_invalid_confirm_patterns() {
+        let error = Config::parse("confirm_scripts = [\"deploy(\"]").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid pattern `deploy(` in confirm_scripts"
+        );
+    }
+
+    #[test]
+    fn it_from_file_names_the_invalid_file() {
+        let dir = TestDir::new();
+        let path = dir.join("invalid.toml");
+        fs::write(&path, "sort = 1").unwrap();
+
+        let error = Config::from_file(&path).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            format!("Invalid config file {}", path.display())
+        );
+        assert!(format!("{error:?}").contains("line 1"));
+    }
+
+    #[test]
+    fn it_from_file_resolves_theme_paths_against_its_directory() {
+        let dir = TestDir::new();
+        let path = dir.join("theme.toml");
+        fs::write(&path, r#"theme = "themes/mine.toml""#).unwrap();
+
+        let config = Config::from_file(&path).unwrap();
+
+        assert_eq!(
+            config.theme,
+            Some(dir.join("themes/mine.toml").to_string_lossy().into_owned())
+        );
+        assert_eq!(resolve_theme_path("dark".to_string(), &dir), "dark");
+        assert_eq!(
+            resolve_theme_path("/etc/mine.toml".to_string(), &dir),
+            "/etc/mine.toml"
+        );
+        assert_eq!(
+            resolve_theme_path("~/mine.toml".to_string(), &dir),
+            dirs::home_dir()
+                .unwrap()
+                .join("mine.toml")
+                .to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn it_from_file_treats_missing_file_as_empty() {
+        let config = Config::from_file(Path::new("/no/such/config.toml")).unwrap();
+
+        assert_eq!(config.package_manager, None);
+        assert!(config.keys.is_empty());
+    }
+}
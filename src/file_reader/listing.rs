@@ -0,0 +1,139 @@
+use crate::file_reader::scripts::Scripts;
+use crate::runner::{package_manager::PackageManager, run_request::RunRequest};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Version of the `scriptor list --json` output. Fields may be added within a version, but any
+/// change that could break a reader bumps it.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The scripts in a project as printed by `scriptor list --json`.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Listing {
+    pub version: u32,
+    pub scripts: Vec<ListedScript>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ListedScript {
+    pub name: String,
+    pub command: String,
+    /// The file the script is defined in.
+    pub source: PathBuf,
+    /// The `name` of the package defining the script, if it has one.
+    pub package: Option<String>,
+    pub description: Option<String>,
+    /// The package manager scriptor runs the script with.
+    pub runner: PackageManager,
+    /// The full command line scriptor runs, as `--print` shows it.
+    pub run: String,
+}
+
+impl Listing {
+    /// Lists `scripts` sorted by name.
+    pub fn new(
+        scripts: &Scripts,
+        source: &Path,
+        package: Option<String>,
+        runner: PackageManager,
+    ) -> Self {
+        let project_dir = source.parent().unwrap_or(source).to_path_buf();
+
+        let mut listed: Vec<ListedScript> = scripts
+            .scripts
+            .iter()
+            .map(|(name, command)| ListedScript {
+                name: name.clone(),
+                command: command.clone(),
+                source: source.to_path_buf(),
+                package: package.clone(),
+                description: scripts.descriptions.get(name).cloned(),
+                runner,
+                run: RunRequest::new(name.clone(), vec![], project_dir.clone(), runner)
+                    .command_line(),
+            })
+            .collect();
+        listed.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            version: SCHEMA_VERSION,
+            scripts: listed,
+        }
+    }
+
+    /// One line per script with its name and command, for reading in a terminal.
+    pub fn to_text(&self) -> String {
+        let width = self
+            .scripts
+            .iter()
+            .map(|script| script.name.len())
+            .max()
+            .unwrap_or(0);
+
+        self.scripts
+            .iter()
+            .map(|script| format!("{:width$}  {}\n", script.name, script.command))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn listing() -> Listing {
+        let scripts = Scripts {
+            scripts: HashMap::from([
+                ("test".to_string(), "jest".to_string()),
+                ("build".to_string(), "tsc".to_string()),
+            ]),
+            descriptions: HashMap::from([("build".to_string(), "Compiles".to_string())]),
+        };
+
+        Listing::new(
+            &scripts,
+            Path::new("/app/package.json"),
+            Some("app".to_string()),
+            PackageManager::Npm,
+        )
+    }
+
+    #[test]
+    fn it_serializes_to_documented_schema() {
+        let value = serde_json::to_value(listing()).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "version": 1,
+                "scripts": [
+                    {
+                        "name": "build",
+                        "command": "tsc",
+                        "source": "/app/package.json",
+                        "package": "app",
+                        "description": "Compiles",
+                        "runner": "npm",
+                        "run": "npm run build"
+                    },
+                    {
+                        "name": "test",
+                        "command": "jest",
+                        "source": "/app/package.json",
+                        "package": "app",
+                        "description": null,
+                        "runner": "npm",
+                        "run": "npm run test"
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn it_to_text_aligns_commands() {
+        assert_eq!(listing().to_text(), "build  tsc\ntest   jest\n");
+    }
+}
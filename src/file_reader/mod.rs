@@ -1,2 +1,5 @@
+pub mod listing;
 mod read_json;
 pub mod scripts;
+#[cfg(feature = "process")]
+pub mod watcher;
@@ -1,16 +1,95 @@
-use serde::{Deserialize, Serialize};
-use serde_json::Result;
-use std::{collections::HashMap, fs};
+use serde::Deserialize;
+use serde_json::{Result, Value};
+use std::{collections::HashMap, fs, path::Path};
+
+#[derive(Deserialize)]
+struct PackageJson {
+    scripts: HashMap<String, Value>,
+    #[serde(rename = "scripts-info", default)]
+    scripts_info: HashMap<String, Value>,
+    #[serde(default)]
+    ntl: Ntl,
+}
+
+#[derive(Deserialize)]
+struct PackageName {
+    name: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct Ntl {
+    #[serde(default)]
+    descriptions: HashMap<String, Value>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ScriptList {
+    pub scripts: HashMap<String, String>,
+    pub descriptions: HashMap<String, String>,
+}
+
+impl From<PackageJson> for ScriptList {
+    /// Separates `//` comment keys from the scripts and collects descriptions, preferring
+    /// `scripts-info`, then `ntl.descriptions`, then a `//<name>` comment key.
+    fn from(package: PackageJson) -> Self {
+        let mut scripts = HashMap::new();
+        let mut descriptions = HashMap::new();
+
+        for (key, value) in package.scripts {
+            let Value::String(value) = value else {
+                continue;
+            };
+
+            match key.strip_prefix("//").map(str::trim) {
+                Some("") => {}
+                Some(name) => {
+                    descriptions.insert(name.to_string(), value);
+                }
+                None => {
+                    scripts.insert(key, value);
+                }
+            }
+        }
+
+        for (name, description) in package
+            .ntl
+            .descriptions
+            .into_iter()
+            .chain(package.scripts_info)
+        {
+            if let Value::String(description) = description {
+                descriptions.insert(name, description);
+            }
+        }
+
+        descriptions.retain(|name, _| scripts.contains_key(name));
+
+        Self {
+            scripts,
+            descriptions,
+        }
+    }
+}
 
-#[derive(Serialize, Deserialize)]
-struct ScriptList {
-    scripts: HashMap<String, String>,
+/// Reads the scripts like `read_scripts`, but without the placeholder entries it shows when the
+/// file is missing or invalid.
+pub fn try_read_scripts(path: &Path) -> Option<ScriptList> {
+    let data = fs::read_to_string(path).ok()?;
+
+    serde_json::from_str::<PackageJson>(&data)
+        .ok()
+        .map(ScriptList::from)
 }
 
-pub fn read_scripts(filename: &str) -> std::io::Result<HashMap<String, String>> {
-    let current_dir = std::env::current_dir()?;
+/// Reads the `name` field of the package.
+pub fn read_package_name(path: &Path) -> Option<String> {
+    let data = fs::read_to_string(path).ok()?;
+
+    serde_json::from_str::<PackageName>(&data).ok()?.name
+}
 
-    let data = fs::read_to_string(current_dir.join(filename));
+pub fn read_scripts(path: &Path) -> std::io::Result<ScriptList> {
+    let data = fs::read_to_string(path);
 
     let result = data.unwrap_or_else(|_d| {
         r#"
@@ -22,25 +101,35 @@ pub fn read_scripts(filename: &str) -> std::io::Result<HashMap<String, String>>
         .to_string()
     });
 
-    let json_result: Result<ScriptList> = serde_json::from_str(&result);
+    let json_result: Result<PackageJson> = serde_json::from_str(&result);
 
-    let json = json_result.unwrap_or_else(|_error| ScriptList {
-        scripts: HashMap::from([(
-            "Error parsing json".to_owned(),
-            "There was an error parsing your package.json".to_owned(),
-        )]),
-    });
+    let json = json_result
+        .map(ScriptList::from)
+        .unwrap_or_else(|_error| ScriptList {
+            scripts: HashMap::from([(
+                "Error parsing json".to_owned(),
+                "There was an error parsing your package.json".to_owned(),
+            )]),
+            descriptions: HashMap::new(),
+        });
 
-    Ok(json.scripts)
+    Ok(json)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
+
+    fn test_data(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/file_reader/test_data")
+            .join(name)
+    }
 
     #[test]
     fn it_no_scripts_returns_error() {
-        let scripts = read_scripts("src/file_reader/test_data/no_scripts.json").unwrap();
+        let scripts = read_scripts(&test_data("no_scripts.json")).unwrap().scripts;
 
         let expected = HashMap::from([(
             "Error parsing json".to_string(),
@@ -52,7 +141,9 @@ mod tests {
 
     #[test]
     fn it_bad_json_format_returns_error() {
-        let scripts = read_scripts("src/file_reader/test_data/bad_formatting.json").unwrap();
+        let scripts = read_scripts(&test_data("bad_formatting.json"))
+            .unwrap()
+            .scripts;
 
         let expected = HashMap::from([(
             "Error parsing json".to_string(),
@@ -64,7 +155,7 @@ mod tests {
 
     #[test]
     fn it_no_file_returns_error() {
-        let scripts = read_scripts("src/file_reader/test_data/no_file.json").unwrap();
+        let scripts = read_scripts(&test_data("no_file.json")).unwrap().scripts;
 
         let expected = HashMap::from([(
             "file_not_found".to_string(),
@@ -76,7 +167,7 @@ mod tests {
 
     #[test]
     fn it_read_scripts_returns_scripts() {
-        let scripts = read_scripts("src/file_reader/test_data/scripts.json").unwrap();
+        let scripts = read_scripts(&test_data("scripts.json")).unwrap().scripts;
 
         let expected = HashMap::from([
             ("android".to_string(), "yarn android".to_string()),
@@ -88,4 +179,24 @@ mod tests {
 
         assert_eq!(scripts, expected);
     }
+
+    #[test]
+    fn it_read_scripts_returns_descriptions() {
+        let script_list = read_scripts(&test_data("descriptions.json")).unwrap();
+
+        let expected_scripts = HashMap::from([
+            ("build".to_string(), "tsc".to_string()),
+            ("lint".to_string(), "eslint .".to_string()),
+            ("start".to_string(), "node index.js".to_string()),
+            ("test".to_string(), "jest".to_string()),
+        ]);
+        let expected_descriptions = HashMap::from([
+            ("build".to_string(), "Builds the app".to_string()),
+            ("lint".to_string(), "Lints from a comment key".to_string()),
+            ("start".to_string(), "Starts the server".to_string()),
+        ]);
+
+        assert_eq!(script_list.scripts, expected_scripts);
+        assert_eq!(script_list.descriptions, expected_descriptions);
+    }
 }
@@ -1,18 +1,109 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
-use crate::file_reader::read_json::read_scripts;
+use crate::config::settings::Config;
+use crate::file_reader::read_json::{read_package_name, read_scripts, try_read_scripts};
 use serde::{Deserialize, Serialize};
 
+const PACKAGE_JSON: &str = "package.json";
+
 #[derive(Serialize, Deserialize)]
 pub struct Scripts {
-    pub scripts: HashMap<String, String>,
+    pub(crate) scripts: HashMap<String, String>,
+    pub(crate) descriptions: HashMap<String, String>,
 }
 
 impl Scripts {
-    pub fn new() -> Self {
-        let scripts = read_scripts("package.json").unwrap();
+    /// Reads the scripts from package.json in `project_dir`, showing a placeholder script
+    /// explaining the problem when it is missing or invalid.
+    pub fn load(project_dir: &Path) -> Self {
+        Self::from_file(&project_dir.join(PACKAGE_JSON))
+    }
+
+    /// Reads the scripts from the package.json at `path`, like `load`.
+    pub fn from_file(path: &Path) -> Self {
+        let script_list = read_scripts(path).unwrap();
+
+        Self {
+            scripts: script_list.scripts,
+            descriptions: script_list.descriptions,
+        }
+    }
+
+    /// Reads the scripts from package.json in `project_dir`, returning `None` rather than
+    /// placeholder entries when it is missing or invalid.
+    pub fn read(project_dir: &Path) -> Option<Self> {
+        let script_list = try_read_scripts(&project_dir.join(PACKAGE_JSON))?;
+
+        Some(Self {
+            scripts: script_list.scripts,
+            descriptions: script_list.descriptions,
+        })
+    }
+
+    /// Describes scripts by name, replacing any descriptions read from package.json.
+    pub fn with_descriptions(mut self, descriptions: HashMap<String, String>) -> Self {
+        self.descriptions = descriptions;
+        self
+    }
+
+    /// Each script's command, by name.
+    pub fn commands(&self) -> &HashMap<String, String> {
+        &self.scripts
+    }
+
+    pub fn command(&self, name: &str) -> Option<&str> {
+        self.scripts.get(name).map(String::as_str)
+    }
 
-        Self { scripts }
+    /// The description of a script, from `scripts-info`, `ntl.descriptions` or a `//<name>`
+    /// comment in package.json.
+    pub fn description(&self, name: &str) -> Option<&str> {
+        self.descriptions.get(name).map(String::as_str)
+    }
+
+    /// Drops the scripts the config hides.
+    pub fn hide(&mut self, config: &Config) {
+        self.scripts.retain(|name, _| !config.is_hidden(name));
+        self.descriptions.retain(|name, _| !config.is_hidden(name));
+    }
+
+    /// Whether the script called `name` matches a search for `query` by its name or description.
+    pub fn matches(&self, name: &str, query: &str) -> bool {
+        let description = self.descriptions.get(name);
+
+        name.contains(query) || description.is_some_and(|d| d.contains(query))
+    }
+
+    /// The scripts matching a search for `query`, as the picker shows them.
+    pub fn filter(&self, query: &str) -> HashMap<String, String> {
+        self.scripts
+            .iter()
+            .filter(|(name, _)| self.matches(name, query))
+            .map(|(name, command)| (name.clone(), command.clone()))
+            .collect()
+    }
+
+    /// The files `load` reads scripts from, used to watch for changes.
+    pub fn source_files(project_dir: &Path) -> Vec<PathBuf> {
+        vec![project_dir.join(PACKAGE_JSON)]
+    }
+
+    /// The `name` of the package in `project_dir`.
+    pub fn package_name(project_dir: &Path) -> Option<String> {
+        read_package_name(&project_dir.join(PACKAGE_JSON))
+    }
+}
+
+/// Scripts without descriptions, for building a set in memory along with `with_descriptions`.
+impl From<HashMap<String, String>> for Scripts {
+    fn from(scripts: HashMap<String, String>) -> Self {
+        Self {
+            scripts,
+            descriptions: HashMap::new(),
+        }
     }
 }
 
@@ -20,9 +111,15 @@ impl Scripts {
 mod tests {
     use super::*;
 
+    fn test_data(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/file_reader/test_data")
+            .join(name)
+    }
+
     #[test]
     fn it_scripts_instantiates_correctly() {
-        let scripts = Scripts::new();
+        let scripts = Scripts::from_file(&test_data("scripts.json"));
 
         let expected = HashMap::from([
             ("android".to_string(), "yarn android".to_string()),
@@ -34,4 +131,58 @@ mod tests {
 
         assert_eq!(scripts.scripts, expected);
     }
+
+    #[test]
+    fn it_read_matches_load_for_valid_package_json() {
+        let project_dir = test_data("project");
+        let scripts = Scripts::read(&project_dir).unwrap();
+
+        assert_eq!(scripts.scripts, Scripts::load(&project_dir).scripts);
+        assert_eq!(
+            Scripts::package_name(&project_dir).as_deref(),
+            Some("fixture")
+        );
+    }
+
+    #[test]
+    fn it_read_returns_none_without_package_json() {
+        assert!(Scripts::read(&test_data("missing")).is_none());
+    }
+
+    #[test]
+    fn it_hide_removes_hidden_scripts() {
+        let mut scripts = Scripts::from_file(&test_data("scripts.json"));
+        let config = Config::parse(r#"hidden_scripts = ["an*", "ios"]"#).unwrap();
+
+        scripts.hide(&config);
+
+        let expected = HashMap::from([
+            ("run".to_string(), "expo start".to_string()),
+            ("start".to_string(), "yarn start".to_string()),
+            ("test".to_string(), "jest".to_string()),
+        ]);
+
+        assert_eq!(scripts.scripts, expected);
+    }
+
+    #[test]
+    fn it_filter_matches_names_and_descriptions() {
+        let scripts = Scripts {
+            scripts: HashMap::from([
+                ("build".to_string(), "tsc".to_string()),
+                ("test".to_string(), "jest".to_string()),
+            ]),
+            descriptions: HashMap::from([("test".to_string(), "Runs the suite".to_string())]),
+        };
+
+        assert_eq!(
+            scripts.filter("bu").into_keys().collect::<Vec<_>>(),
+            ["build"]
+        );
+        assert_eq!(
+            scripts.filter("suite").into_keys().collect::<Vec<_>>(),
+            ["test"]
+        );
+        assert_eq!(scripts.filter("").len(), 2);
+    }
 }
@@ -0,0 +1,94 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::{collections::HashSet, ffi::OsString, path::PathBuf};
+
+/// Calls `on_change` from a background thread whenever any of `files` change, for as long as
+/// the returned watcher is kept.
+///
+/// The parent directories are watched rather than the files themselves, as editors and git
+/// commonly replace a file instead of writing to it, which would drop a watch on the file.
+pub fn watch_sources(
+    files: &[PathBuf],
+    on_change: impl Fn() + Send + 'static,
+) -> notify::Result<RecommendedWatcher> {
+    let file_names: HashSet<OsString> = files
+        .iter()
+        .filter_map(|file| file.file_name())
+        .map(|name| name.to_os_string())
+        .collect();
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if event.is_ok_and(|event| changes_source(&event, &file_names)) {
+            on_change();
+        }
+    })?;
+
+    let directories: HashSet<PathBuf> = files
+        .iter()
+        .filter_map(|file| file.parent())
+        .map(|parent| parent.to_path_buf())
+        .collect();
+
+    for directory in directories {
+        watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+    }
+
+    Ok(watcher)
+}
+
+/// Whether `event` changed one of the files named in `file_names`, rather than only reading it
+/// or touching another file in the same directory.
+fn changes_source(event: &notify::Event, file_names: &HashSet<OsString>) -> bool {
+    let touches_source = event.paths.iter().any(|path| {
+        path.file_name()
+            .is_some_and(|name| file_names.contains(name))
+    });
+
+    touches_source && !event.kind.is_access()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use notify::{event::AccessKind, Event, EventKind};
+    use std::{fs, sync::mpsc, time::Duration};
+
+    #[test]
+    fn it_changes_source_ignores_other_files_and_reads() {
+        let file_names = HashSet::from([OsString::from("package.json")]);
+        let event = |kind: EventKind, path: &str| Event::new(kind).add_path(PathBuf::from(path));
+
+        assert!(changes_source(
+            &event(EventKind::Any, "/app/package.json"),
+            &file_names
+        ));
+        assert!(!changes_source(
+            &event(EventKind::Any, "/app/other.json"),
+            &file_names
+        ));
+        assert!(!changes_source(
+            &event(EventKind::Access(AccessKind::Any), "/app/package.json"),
+            &file_names
+        ));
+    }
+
+    #[test]
+    fn it_reports_changes_to_watched_file() {
+        let dir = TestDir::new();
+        let files = vec![dir.join("package.json")];
+        fs::write(&files[0], "{}").unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let _watcher = watch_sources(&files, move || {
+            let _ = sender.send(());
+        })
+        .unwrap();
+
+        fs::write(&files[0], r#"{ "scripts": {} }"#).unwrap();
+
+        // Notifications arrive on another thread, so give them time on a loaded machine
+        receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("no change reported");
+    }
+}
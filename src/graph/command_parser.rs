@@ -0,0 +1,245 @@
+/// A reference to another script found in a command, either an exact name or an npm-run-all
+/// style pattern such as `build:*`.
+#[derive(Debug, PartialEq)]
+pub struct ScriptReference(pub String);
+
+const PACKAGE_MANAGERS: [&str; 4] = ["npm", "yarn", "pnpm", "bun"];
+const RUN_ALL: [&str; 3] = ["run-s", "run-p", "npm-run-all"];
+const NPM_RUN: [&str; 4] = ["run", "run-script", "rum", "urn"];
+const NPM_LIFECYCLE: [&str; 4] = ["start", "test", "stop", "restart"];
+// Flags taking the next token as their value, as in `pnpm -C web build`
+const VALUE_FLAGS: [&str; 8] = [
+    "-C",
+    "--dir",
+    "-F",
+    "--filter",
+    "--prefix",
+    "--cwd",
+    "-w",
+    "--workspace",
+];
+
+/// Finds every script a shell command invokes through a package manager, `run-s`/`run-p`/
+/// `npm-run-all` or `concurrently`.
+pub fn parse_references(command: &str) -> Vec<ScriptReference> {
+    split_commands(command)
+        .iter()
+        .flat_map(|tokens| parse_segment(tokens))
+        .collect()
+}
+
+/// Tokenizes a command, honouring quotes and escapes, and splits it on `&&`, `||`, `;`, `|`
+/// and `&` into the simple commands it is made of.
+fn split_commands(command: &str) -> Vec<Vec<String>> {
+    let mut segments = vec![];
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut in_token = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_token = true;
+                token.extend(chars.by_ref().take_while(|c| *c != '\''));
+            }
+            '"' => {
+                in_token = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => token.extend(chars.next()),
+                        c => token.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_token = true;
+                token.extend(chars.next());
+            }
+            '&' | '|' | ';' => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut token));
+                    in_token = false;
+                }
+                if chars.peek() == Some(&c) {
+                    chars.next();
+                }
+                segments.push(std::mem::take(&mut tokens));
+            }
+            c if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut token));
+                    in_token = false;
+                }
+            }
+            c => {
+                in_token = true;
+                token.push(c);
+            }
+        }
+    }
+
+    if in_token {
+        tokens.push(token);
+    }
+    segments.push(tokens);
+
+    segments.retain(|tokens| !tokens.is_empty());
+    segments
+}
+
+fn is_env_assignment(token: &str) -> bool {
+    token.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// The arguments that aren't flags or the values of flags.
+fn positional_args<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut args = vec![];
+
+    while let Some(token) = tokens.next() {
+        if VALUE_FLAGS.contains(&token) {
+            tokens.next();
+        } else if !token.starts_with('-') {
+            args.push(token);
+        }
+    }
+
+    args
+}
+
+fn parse_segment(tokens: &[String]) -> Vec<ScriptReference> {
+    let mut tokens = tokens.iter().map(String::as_str).peekable();
+
+    // Skip environment setup such as `NODE_ENV=test` or `cross-env NODE_ENV=test`
+    while tokens
+        .next_if(|token| is_env_assignment(token) || ["env", "cross-env", "npx"].contains(token))
+        .is_some()
+    {}
+
+    let Some(program) = tokens.next() else {
+        return vec![];
+    };
+    let mut args = positional_args(tokens).into_iter();
+
+    let reference = |name: &str| ScriptReference(name.to_string());
+
+    match program {
+        "npm" => match args.next() {
+            Some(subcommand) if NPM_RUN.contains(&subcommand) => {
+                args.next().map(reference).into_iter().collect()
+            }
+            Some("t") => vec![reference("test")],
+            Some(subcommand) if NPM_LIFECYCLE.contains(&subcommand) => vec![reference(subcommand)],
+            _ => vec![],
+        },
+        program if PACKAGE_MANAGERS.contains(&program) => {
+            let mut subcommand = args.next();
+
+            // `yarn workspace <name> <script>` names the workspace before the script
+            if subcommand == Some("workspace") {
+                args.next();
+                subcommand = args.next();
+            }
+
+            match subcommand {
+                Some("run") => args.next().map(reference).into_iter().collect(),
+                Some(script) => vec![reference(script)],
+                None => vec![],
+            }
+        }
+        program if RUN_ALL.contains(&program) => args.map(reference).collect(),
+        "concurrently" => args
+            .flat_map(|arg| {
+                let shorthand = PACKAGE_MANAGERS
+                    .iter()
+                    .find_map(|manager| arg.strip_prefix(manager)?.strip_prefix(':'));
+
+                match shorthand {
+                    Some(script) => vec![reference(script)],
+                    None => parse_references(arg),
+                }
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(command: &str) -> Vec<String> {
+        parse_references(command)
+            .into_iter()
+            .map(|reference| reference.0)
+            .collect()
+    }
+
+    #[test]
+    fn it_split_commands_honours_operators_and_quotes() {
+        let segments = split_commands(r#"yarn lint && echo "a && b" | grep 'x;y'; yarn test&"#);
+
+        assert_eq!(
+            segments,
+            vec![
+                vec!["yarn", "lint"],
+                vec!["echo", "a && b"],
+                vec!["grep", "x;y"],
+                vec!["yarn", "test"],
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parses_package_manager_invocations() {
+        assert_eq!(names("npm run build -- --watch"), vec!["build"]);
+        assert_eq!(names("npm test && npm t"), vec!["test", "test"]);
+        assert_eq!(names("npm install"), Vec::<String>::new());
+        assert_eq!(
+            names("yarn lint && yarn run test:unit"),
+            vec!["lint", "test:unit"]
+        );
+        assert_eq!(names("pnpm --silent build"), vec!["build"]);
+    }
+
+    #[test]
+    fn it_skips_flag_values() {
+        assert_eq!(names("pnpm -C web build"), vec!["build"]);
+        assert_eq!(names("pnpm --filter api run test"), vec!["test"]);
+        assert_eq!(names("npm run lint -w app"), vec!["lint"]);
+        assert_eq!(names("yarn --cwd packages/ui storybook"), vec!["storybook"]);
+    }
+
+    #[test]
+    fn it_parses_yarn_workspace_invocations() {
+        assert_eq!(names("yarn workspace app build"), vec!["build"]);
+        assert_eq!(names("yarn workspace app run test:unit"), vec!["test:unit"]);
+        assert_eq!(names("yarn workspace app"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn it_skips_environment_assignments() {
+        assert_eq!(names("NODE_ENV=test yarn jest"), vec!["jest"]);
+        assert_eq!(names("cross-env CI=1 npm run e2e"), vec!["e2e"]);
+    }
+
+    #[test]
+    fn it_parses_run_all_invocations() {
+        assert_eq!(names("run-s clean build:*"), vec!["clean", "build:*"]);
+        assert_eq!(names("npx run-p --race serve test"), vec!["serve", "test"]);
+        assert_eq!(names("npm-run-all -p lint test"), vec!["lint", "test"]);
+    }
+
+    #[test]
+    fn it_parses_concurrently_invocations() {
+        assert_eq!(
+            names(r#"concurrently -k "npm:watch-*" "yarn serve" "tsc -w""#),
+            vec!["watch-*", "serve"]
+        );
+    }
+}
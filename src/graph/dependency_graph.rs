@@ -0,0 +1,147 @@
+use crate::graph::command_parser::{parse_references, ScriptReference};
+use globset::GlobBuilder;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Which scripts call which, built from the `npm run`/`yarn`/`pnpm`/`run-s`/`run-p`/
+/// `concurrently` invocations in each script's command.
+#[derive(Debug, Default, PartialEq)]
+pub struct DependencyGraph {
+    calls: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl DependencyGraph {
+    pub fn from_scripts(scripts: &HashMap<String, String>) -> Self {
+        let calls = scripts
+            .iter()
+            .map(|(name, command)| {
+                let callees = parse_references(command)
+                    .iter()
+                    .flat_map(|reference| resolve(reference, scripts))
+                    .filter(|callee| callee != name)
+                    .collect();
+
+                (name.to_string(), callees)
+            })
+            .collect();
+
+        Self { calls }
+    }
+
+    /// Scripts called by `name`.
+    pub fn callees(&self, name: &str) -> Vec<&str> {
+        self.calls
+            .get(name)
+            .map(|callees| callees.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Scripts that call `name`.
+    pub fn callers(&self, name: &str) -> Vec<&str> {
+        self.calls
+            .iter()
+            .filter(|(_, callees)| callees.contains(name))
+            .map(|(caller, _)| caller.as_str())
+            .collect()
+    }
+
+    /// Renders the graph in Graphviz DOT format, including scripts that call nothing.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph scripts {\n");
+
+        for (caller, callees) in &self.calls {
+            if callees.is_empty() {
+                dot.push_str(&format!("    {};\n", quote(caller)));
+            }
+
+            for callee in callees {
+                dot.push_str(&format!("    {} -> {};\n", quote(caller), quote(callee)));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Resolves a reference to the scripts it names, expanding npm-run-all style wildcards where
+/// `*` stays within one `:` separated segment and `**` spans several.
+fn resolve(reference: &ScriptReference, scripts: &HashMap<String, String>) -> Vec<String> {
+    let ScriptReference(name) = reference;
+
+    if !name.contains('*') {
+        return scripts
+            .contains_key(name)
+            .then(|| name.to_string())
+            .into_iter()
+            .collect();
+    }
+
+    let Ok(glob) = GlobBuilder::new(&name.replace(':', "/"))
+        .literal_separator(true)
+        .build()
+    else {
+        return vec![];
+    };
+    let matcher = glob.compile_matcher();
+
+    let mut matches: Vec<String> = scripts
+        .keys()
+        .filter(|script| matcher.is_match(script.replace(':', "/")))
+        .cloned()
+        .collect();
+    matches.sort();
+
+    matches
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts() -> HashMap<String, String> {
+        HashMap::from([
+            ("ci".to_string(), "yarn lint && yarn test".to_string()),
+            ("lint".to_string(), "eslint .".to_string()),
+            ("test".to_string(), "run-p test:*".to_string()),
+            ("test:unit".to_string(), "jest".to_string()),
+            ("test:e2e".to_string(), "npm test".to_string()),
+            ("test:e2e:ios".to_string(), "detox test".to_string()),
+            ("setup".to_string(), "yarn install".to_string()),
+        ])
+    }
+
+    #[test]
+    fn it_callees_returns_scripts_called() {
+        let graph = DependencyGraph::from_scripts(&scripts());
+
+        assert_eq!(graph.callees("ci"), vec!["lint", "test"]);
+        assert_eq!(graph.callees("test"), vec!["test:e2e", "test:unit"]);
+        assert_eq!(graph.callees("setup"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn it_callers_returns_scripts_calling() {
+        let graph = DependencyGraph::from_scripts(&scripts());
+
+        assert_eq!(graph.callers("test"), vec!["ci", "test:e2e"]);
+        assert_eq!(graph.callers("ci"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn it_to_dot_renders_edges_and_isolated_scripts() {
+        let graph = DependencyGraph::from_scripts(&HashMap::from([
+            ("ci".to_string(), "yarn lint".to_string()),
+            ("lint".to_string(), "eslint .".to_string()),
+            ("say \"hi\"".to_string(), "echo hi".to_string()),
+        ]));
+
+        assert_eq!(
+            graph.to_dot(),
+            "digraph scripts {\n    \"ci\" -> \"lint\";\n    \"lint\";\n    \"say \\\"hi\\\"\";\n}\n"
+        );
+    }
+}
@@ -0,0 +1,2 @@
+mod command_parser;
+pub mod dependency_graph;
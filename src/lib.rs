@@ -0,0 +1,41 @@
+//! Finds the scripts in a project's package.json, filters them and runs them with the project's
+//! package manager. The `scriptor` binary builds its picker on top of this.
+//!
+//! Watching files and capturing runs need the `process` feature, which is on by default.
+//!
+//! ```no_run
+//! use scriptor::{PackageManager, RunRequest, Scripts};
+//!
+//! let cwd = std::env::current_dir().unwrap();
+//! let scripts = Scripts::load(&cwd);
+//!
+//! for name in scripts.filter("test").keys() {
+//!     let request = RunRequest::new(name.clone(), vec![], cwd.clone(), PackageManager::detect(&cwd));
+//!     request.run().unwrap();
+//! }
+//! ```
+
+mod config;
+mod file_reader;
+mod graph;
+mod runner;
+mod storage;
+#[cfg(test)]
+mod test_dir;
+
+pub use config::settings::{Config, ConfirmWith, SortOrder};
+pub use file_reader::{
+    listing::{ListedScript, Listing},
+    scripts::Scripts,
+};
+pub use graph::dependency_graph::DependencyGraph;
+pub use runner::{env::RunEnv, package_manager::PackageManager, run_request::RunRequest};
+pub use storage::{
+    project_store::ProjectStore,
+    run_log::{OutputLine, RunLog, RunLogs, Stream},
+};
+
+#[cfg(feature = "process")]
+pub use file_reader::watcher::watch_sources;
+#[cfg(feature = "process")]
+pub use runner::{capture::run_captured, watch::watch};
@@ -1,24 +1,254 @@
-mod file_reader;
+mod cli;
+mod shell;
+// Only the picker's tests need a directory of their own
+#[cfg(all(test, feature = "tui"))]
+mod test_dir;
+#[cfg(feature = "tui")]
 mod ui;
 
-use std::process::{Command, Stdio};
-
-use crate::ui::render::Render;
-use color_eyre::Result;
+use crate::cli::{Cli, Command};
+use crate::shell::{
+    completions::{completions, script_names},
+    init::init_script,
+};
+#[cfg(feature = "tui")]
+use crate::ui::{
+    app::{App, Selection},
+    keymap::KeyMap,
+    terminal,
+    theme::Theme,
+};
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+#[cfg(feature = "process")]
+use scriptor::{run_captured, watch};
+use scriptor::{
+    Config, DependencyGraph, Listing, PackageManager, ProjectStore, RunEnv, RunLogs, RunRequest,
+    Scripts,
+};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+#[cfg(feature = "process")]
+use std::sync::atomic::Ordering;
 
 fn main() -> Result<()> {
     color_eyre::install()?;
-    let terminal = ratatui::init();
-    let app_result = Render::new().run(terminal);
-    ratatui::restore();
+    let mut cli = Cli::parse_args();
+    let (script, args) = cli.direct_script();
+
+    match cli.command {
+        Some(Command::Init { shell }) => {
+            print!("{}", init_script(shell));
+            return Ok(());
+        }
+        Some(Command::Completions { shell }) => {
+            print!("{}", completions(shell));
+            return Ok(());
+        }
+        Some(Command::List { .. } | Command::Run { .. }) | None => {}
+    }
+
+    let cwd = match &cli.cwd {
+        Some(dir) => dir
+            .canonicalize()
+            .wrap_err_with(|| format!("Could not open project directory {}", dir.display()))?,
+        None => std::env::current_dir()?,
+    };
+
+    if cli.complete {
+        // Completion stays quiet rather than printing errors into the shell
+        if let Some(mut scripts) = Scripts::read(&cwd) {
+            scripts.hide(&Config::load(&cwd).unwrap_or_default());
+            print!("{}", script_names(scripts.commands().keys().cloned()));
+        }
+
+        return Ok(());
+    }
+
+    let config = Config::load(&cwd)?;
+    let package_manager = config.package_manager(&cwd);
+
+    if cli.graph {
+        let scripts = visible_scripts(&cwd, &config)?;
+        print!(
+            "{}",
+            DependencyGraph::from_scripts(scripts.commands()).to_dot()
+        );
+
+        return Ok(());
+    }
+
+    if let Some(Command::List { json }) = cli.command {
+        let scripts = visible_scripts(&cwd, &config)?;
+
+        let listing = Listing::new(
+            &scripts,
+            &cwd.join("package.json"),
+            Scripts::package_name(&cwd),
+            package_manager,
+        );
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&listing)?);
+        } else {
+            print!("{}", listing.to_text());
+        }
+
+        return Ok(());
+    }
+
+    let store = ProjectStore::for_project(&cwd);
+    let log_run = cli.log || config.log_runs();
+
+    let default_env = config.run_env(cli.env_mode.as_deref());
+    let cli_env = RunEnv::from_vars(cli.env);
+    // Picking an env for a run replaces the script's preset, and --env wins over both
+    let env_for = |script: &str, picked: Option<RunEnv>| {
+        default_env
+            .clone()
+            .merge(
+                picked
+                    .or_else(|| store.env_preset(script))
+                    .unwrap_or_default(),
+            )
+            .merge(cli_env.clone())
+    };
+    // History only keeps env files, so the script's preset variables are added back
+    let rerun = |last_run: RunRequest| {
+        let preset = store.env_preset(last_run.script()).unwrap_or_default();
+        let env = last_run.env().clone().merge(preset).merge(cli_env.clone());
+
+        last_run.with_env(env)
+    };
+
+    let request = if cli.last {
+        let last_run = store
+            .last_run()
+            .ok_or_else(|| eyre!("No previous run recorded for {}", cwd.display()))?;
+
+        rerun(last_run)
+    } else if let Some(script) = script {
+        if visible_scripts(&cwd, &config)?.command(&script).is_none() {
+            return Err(eyre!("No script named `{script}` in package.json"));
+        }
+
+        let env = env_for(&script, None);
+        RunRequest::new(script, args, cwd, package_manager).with_env(env)
+    } else {
+        match pick(config, args, cwd, package_manager, env_for, rerun)? {
+            Some(request) => request,
+            // Shell widgets check the exit code to tell a cancelled pick from an empty command
+            None if cli.print => std::process::exit(1),
+            None => return Ok(()),
+        }
+    };
+
+    if cli.print {
+        println!("{}", request.command_line());
+
+        return Ok(());
+    }
+
+    // Only runs count towards --last and recent sorting, not commands that were just printed
+    store.record_run(&request)?;
 
-    let script = app_result?;
+    if !cli.watch.is_empty() {
+        return watch(&request, &cli.watch, catch_interrupts()?);
+    }
 
-    let _output = Command::new("yarn")
-        .arg(script)
-        .stdout(Stdio::inherit())
-        .output()
-        .expect("failed to run");
+    let status = if log_run {
+        catch_interrupts()?;
+        run_captured(&request, &RunLogs::for_project(request.cwd()))?
+    } else {
+        request.run()?
+    };
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
 
     Ok(())
 }
+
+/// Keeps ctrl-c from ending scriptor while it waits on a script, returning a flag set when it
+/// arrives instead.
+#[cfg(feature = "process")]
+fn catch_interrupts() -> Result<&'static AtomicBool> {
+    static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+    ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst))?;
+
+    Ok(&INTERRUPTED)
+}
+
+#[cfg(not(feature = "process"))]
+fn catch_interrupts() -> Result<&'static AtomicBool> {
+    static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+    Ok(&INTERRUPTED)
+}
+
+#[cfg(not(feature = "process"))]
+fn watch(_request: &RunRequest, _patterns: &[String], _interrupted: &AtomicBool) -> Result<()> {
+    Err(eyre!(
+        "scriptor was built without the `process` feature, so it can't watch files"
+    ))
+}
+
+#[cfg(not(feature = "process"))]
+fn run_captured(_request: &RunRequest, _logs: &RunLogs) -> Result<std::process::ExitStatus> {
+    Err(eyre!(
+        "scriptor was built without the `process` feature, so it can't log runs"
+    ))
+}
+
+/// The project's scripts, leaving out the ones the config hides.
+fn visible_scripts(cwd: &Path, config: &Config) -> Result<Scripts> {
+    let source = cwd.join("package.json");
+    let mut scripts = Scripts::read(cwd)
+        .ok_or_else(|| eyre!("Could not read scripts from {}", source.display()))?;
+    scripts.hide(config);
+
+    Ok(scripts)
+}
+
+/// Lets the user pick a script in the TUI, returning `None` if they quit without picking one.
+#[cfg(feature = "tui")]
+fn pick(
+    config: Config,
+    args: Vec<String>,
+    cwd: PathBuf,
+    package_manager: PackageManager,
+    env_for: impl Fn(&str, Option<RunEnv>) -> RunEnv,
+    rerun: impl Fn(RunRequest) -> RunRequest,
+) -> Result<Option<RunRequest>> {
+    let key_map = KeyMap::from_config(config.keys())?;
+    let theme = Theme::load(config.theme())?;
+
+    let terminal = terminal::init()?;
+    let app_result = App::new(&cwd, config, key_map, theme).run(terminal);
+    terminal::restore();
+
+    Ok(app_result?.map(|selection| match selection {
+        Selection::Script { name, env } => {
+            let env = env_for(&name, env);
+            RunRequest::new(name, args, cwd, package_manager).with_env(env)
+        }
+        Selection::Rerun(last_run) => rerun(last_run),
+    }))
+}
+
+#[cfg(not(feature = "tui"))]
+fn pick(
+    _config: Config,
+    _args: Vec<String>,
+    _cwd: PathBuf,
+    _package_manager: PackageManager,
+    _env_for: impl Fn(&str, Option<RunEnv>) -> RunEnv,
+    _rerun: impl Fn(RunRequest) -> RunRequest,
+) -> Result<Option<RunRequest>> {
+    Err(eyre!(
+        "scriptor was built without the `tui` feature, so name a script to run or pass --last"
+    ))
+}
@@ -0,0 +1,182 @@
+use crate::runner::run_request::RunRequest;
+use crate::storage::run_log::{log_line, RunLog, RunLogs, Stream};
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Read, Write},
+    process::{ExitStatus, Stdio},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// How long to wait for the rest of the output once the script has exited.
+const OUTPUT_GRACE: Duration = Duration::from_millis(500);
+
+type LogFile = Arc<Mutex<BufWriter<File>>>;
+
+/// Runs the script like `RunRequest::run`, also writing its output into a new log in `logs`
+/// along with how long it took and how it exited.
+///
+/// The script's output goes through pipes, so tools that only color their output for a terminal
+/// print it plain.
+///
+/// ctrl-c reaches the script as well as the calling program, which should catch it to wait for
+/// the script and record how it exited.
+pub fn run_captured(request: &RunRequest, logs: &RunLogs) -> io::Result<ExitStatus> {
+    logs.create_dir()?;
+
+    let mut log = RunLog::new(
+        request.script.clone(),
+        // Leaving out the environment, which can hold secrets
+        request.quoted_command(),
+        SystemTime::now(),
+    );
+    let file: LogFile = Arc::new(Mutex::new(BufWriter::new(File::create(
+        logs.output_path(&log),
+    )?)));
+    let start = Instant::now();
+
+    let spawned = request
+        .command()
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(error) => {
+            let _ = fs::remove_file(logs.output_path(&log));
+            return Err(error);
+        }
+    };
+
+    let copies = [
+        child
+            .stdout
+            .take()
+            .map(|stdout| copy_output(stdout, io::stdout(), Stream::Out, start, file.clone())),
+        child
+            .stderr
+            .take()
+            .map(|stderr| copy_output(stderr, io::stderr(), Stream::Err, start, file.clone())),
+    ];
+
+    let status = child.wait()?;
+
+    let copies: Vec<_> = copies.into_iter().flatten().collect();
+    wait_for_output(&copies, OUTPUT_GRACE);
+    if let Ok(mut file) = file.lock() {
+        file.flush()?;
+    }
+
+    log.finish(start.elapsed(), status.code());
+    logs.save(&log)?;
+
+    Ok(status)
+}
+
+/// Waits for the output copies to finish, but at most `grace`. A background process the script
+/// started can keep its pipes open long after the script exits, and its output then goes
+/// unlogged rather than holding scriptor up.
+fn wait_for_output(copies: &[JoinHandle<()>], grace: Duration) {
+    let deadline = Instant::now() + grace;
+
+    while copies.iter().any(|copy| !copy.is_finished()) && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Passes output on to the terminal as it arrives, and writes each complete line to the log.
+fn copy_output(
+    mut source: impl Read + Send + 'static,
+    mut terminal: impl Write + Send + 'static,
+    stream: Stream,
+    start: Instant,
+    file: LogFile,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut buffer = [0; 8192];
+        let mut line = Vec::new();
+
+        let write_line = |line: &[u8]| {
+            let text = String::from_utf8_lossy(line);
+            let text = text.trim_end_matches(['\n', '\r']);
+
+            if let Ok(mut file) = file.lock() {
+                let _ = file.write_all(log_line(start.elapsed(), stream, text).as_bytes());
+            }
+        };
+
+        loop {
+            let read = match source.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => read,
+            };
+
+            let _ = terminal.write_all(&buffer[..read]);
+            let _ = terminal.flush();
+
+            for &byte in &buffer[..read] {
+                line.push(byte);
+
+                if byte == b'\n' {
+                    write_line(&line);
+                    line.clear();
+                }
+            }
+        }
+
+        if !line.is_empty() {
+            write_line(&line);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::{fs, sync::mpsc};
+
+    #[test]
+    fn it_copy_output_logs_each_line() {
+        let dir = TestDir::new();
+        let path = dir.join("capture.log");
+        let file: LogFile = Arc::new(Mutex::new(BufWriter::new(File::create(&path).unwrap())));
+
+        let source: &[u8] = b"built\r\nno newline";
+        copy_output(
+            source,
+            io::sink(),
+            Stream::Err,
+            Instant::now(),
+            file.clone(),
+        )
+        .join()
+        .unwrap();
+        file.lock().unwrap().flush().unwrap();
+
+        let output = fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = output.lines().map(|line| &line[10..]).collect();
+
+        assert_eq!(lines, vec!["err built", "err no newline"]);
+    }
+
+    #[test]
+    fn it_wait_for_output_gives_up_on_open_pipes() {
+        let (sender, receiver) = mpsc::channel::<()>();
+        let copies = [
+            thread::spawn(|| {}),
+            // Stands in for a pipe a background process still holds open
+            thread::spawn(move || {
+                let _ = receiver.recv();
+            }),
+        ];
+
+        let start = Instant::now();
+        wait_for_output(&copies, Duration::from_millis(50));
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(!copies[1].is_finished());
+        drop(sender);
+    }
+}
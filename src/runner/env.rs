@@ -0,0 +1,238 @@
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Environment for a run: `.env` style files loaded in order, then variables set directly,
+/// each overriding what came before.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct RunEnv {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) files: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) vars: BTreeMap<String, String>,
+}
+
+impl RunEnv {
+    /// Variables set directly, without any files.
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self {
+            files: vec![],
+            vars: vars.into_iter().collect(),
+        }
+    }
+
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn vars(&self) -> &BTreeMap<String, String> {
+        &self.vars
+    }
+
+    /// `.env`, followed by `.env.<mode>` when there is a mode.
+    pub fn dotenv(mode: Option<&str>) -> Self {
+        let mut files = vec![PathBuf::from(".env")];
+        files.extend(mode.map(|mode| PathBuf::from(format!(".env.{mode}"))));
+
+        Self {
+            files,
+            vars: BTreeMap::new(),
+        }
+    }
+
+    /// Parses shell-style words, where `KEY=value` sets a variable and anything else names a
+    /// file to load, as in `.env.test DEBUG=1`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let words = shell_words::split(text).map_err(|error| eyre!("{error}"))?;
+        let mut env = Self::default();
+
+        for word in words {
+            match word.split_once('=') {
+                Some((key, _)) if !is_key(key) => {
+                    return Err(eyre!("Invalid variable name `{key}`"));
+                }
+                Some((key, value)) => {
+                    env.vars.insert(key.to_string(), value.to_string());
+                }
+                None => env.files.push(PathBuf::from(word)),
+            }
+        }
+
+        Ok(env)
+    }
+
+    /// The files and variables as `parse` reads them.
+    pub fn to_text(&self) -> String {
+        let files = self
+            .files
+            .iter()
+            .map(|file| shell_words::quote(&file.to_string_lossy()).into_owned());
+
+        files
+            .chain(self.assignments())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The variables as `KEY=value` shell assignments.
+    pub fn assignments(&self) -> impl Iterator<Item = String> + '_ {
+        self.vars.iter().map(|(key, value)| assignment(key, value))
+    }
+
+    /// Only the files, leaving out the variables set directly.
+    pub fn files_only(&self) -> Self {
+        Self {
+            files: self.files.clone(),
+            vars: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.vars.is_empty()
+    }
+
+    /// Layers `over` on top, loading its files after these and letting its variables win.
+    pub fn merge(mut self, over: Self) -> Self {
+        for file in over.files {
+            if !self.files.contains(&file) {
+                self.files.push(file);
+            }
+        }
+        self.vars.extend(over.vars);
+
+        self
+    }
+
+    /// The variables to set, reading the files relative to `cwd`. Files that are missing are
+    /// skipped, as a project only has some of them.
+    pub fn resolve(&self, cwd: &Path) -> BTreeMap<String, String> {
+        let mut vars = BTreeMap::new();
+
+        for file in &self.files {
+            if let Ok(contents) = fs::read_to_string(cwd.join(file)) {
+                vars.extend(parse_dotenv(&contents));
+            }
+        }
+        vars.extend(self.vars.clone());
+
+        vars
+    }
+}
+
+/// Reads `KEY=value` lines, skipping blank lines, `#` comments and lines that aren't
+/// assignments. Values may be quoted, and double quoted values understand `\n` escapes.
+pub fn parse_dotenv(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+
+            is_key(key).then(|| (key.to_string(), unquote(value.trim())))
+        })
+        .collect()
+}
+
+/// A `KEY=value` shell assignment, quoting the value where needed.
+pub(crate) fn assignment(key: &str, value: &str) -> String {
+    format!("{key}={}", shell_words::quote(value))
+}
+
+fn is_key(key: &str) -> bool {
+    let mut chars = key.chars();
+
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn unquote(value: &str) -> String {
+    let quoted = |quote: char| {
+        value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+    };
+
+    if let Some(value) = quoted('"') {
+        value.replace("\\n", "\n").replace("\\\"", "\"")
+    } else if let Some(value) = quoted('\'') {
+        value.to_string()
+    } else {
+        // Unquoted values can end in a comment
+        match value.split_once(" #") {
+            Some((value, _)) => value.trim_end().to_string(),
+            None => value.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    #[test]
+    fn it_parse_dotenv_reads_assignments() {
+        let vars = parse_dotenv(
+            r#"
+            # Comment
+            DEBUG=1
+            export NODE_ENV=test
+            GREETING="hello\nworld"
+            RAW='a "b"'
+            PORT=3000 # dev server
+            not an assignment
+            "#,
+        );
+
+        assert_eq!(
+            vars,
+            vec![
+                ("DEBUG".to_string(), "1".to_string()),
+                ("NODE_ENV".to_string(), "test".to_string()),
+                ("GREETING".to_string(), "hello\nworld".to_string()),
+                ("RAW".to_string(), "a \"b\"".to_string()),
+                ("PORT".to_string(), "3000".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parse_splits_files_and_vars() {
+        let env = RunEnv::parse(r#".env.test DEBUG=1 MESSAGE="a b""#).unwrap();
+
+        assert_eq!(env.files, vec![PathBuf::from(".env.test")]);
+        assert_eq!(env.vars["DEBUG"], "1");
+        assert_eq!(env.vars["MESSAGE"], "a b");
+        assert_eq!(RunEnv::parse(&env.to_text()).unwrap(), env);
+    }
+
+    #[test]
+    fn it_parse_rejects_invalid_names() {
+        let error = RunEnv::parse("1X=2").unwrap_err();
+
+        assert_eq!(error.to_string(), "Invalid variable name `1X`");
+    }
+
+    #[test]
+    fn it_resolve_layers_files_then_vars() {
+        let dir = TestDir::new();
+        fs::write(dir.join(".env"), "A=env\nB=env\n").unwrap();
+        fs::write(dir.join(".env.test"), "B=test\n").unwrap();
+
+        let env = RunEnv::dotenv(Some("test")).merge(RunEnv::parse("C=var .env.missing").unwrap());
+        let vars = env.resolve(&dir);
+
+        assert_eq!(vars["A"], "env");
+        assert_eq!(vars["B"], "test");
+        assert_eq!(vars["C"], "var");
+        assert_eq!(vars.len(), 3);
+    }
+}
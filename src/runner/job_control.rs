@@ -0,0 +1,24 @@
+//! Handing the terminal between scriptor and scripts started in a process group of their own.
+
+/// Makes `group` the terminal's foreground process group, so it reads the terminal's input and
+/// gets ctrl-c. Does nothing when stdin isn't a terminal.
+///
+/// Only calls async-signal-safe functions, so it can run in a child between fork and exec.
+pub fn give_terminal(group: libc::pid_t) {
+    unsafe {
+        if libc::isatty(libc::STDIN_FILENO) == 0 {
+            return;
+        }
+
+        // Changing the foreground group from a background group raises SIGTTOU, which would
+        // stop the process
+        let previous = libc::signal(libc::SIGTTOU, libc::SIG_IGN);
+        libc::tcsetpgrp(libc::STDIN_FILENO, group);
+        libc::signal(libc::SIGTTOU, previous);
+    }
+}
+
+/// Takes the terminal back for scriptor's own process group.
+pub fn take_terminal() {
+    give_terminal(unsafe { libc::getpgrp() });
+}
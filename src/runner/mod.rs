@@ -0,0 +1,9 @@
+#[cfg(feature = "process")]
+pub mod capture;
+pub mod env;
+#[cfg(all(unix, feature = "process"))]
+pub(crate) mod job_control;
+pub mod package_manager;
+pub mod run_request;
+#[cfg(feature = "process")]
+pub mod watch;
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    Npm,
+    #[default]
+    Yarn,
+    Pnpm,
+    Bun,
+}
+
+const LOCKFILES: [(&str, PackageManager); 5] = [
+    ("pnpm-lock.yaml", PackageManager::Pnpm),
+    ("bun.lock", PackageManager::Bun),
+    ("bun.lockb", PackageManager::Bun),
+    ("package-lock.json", PackageManager::Npm),
+    ("yarn.lock", PackageManager::Yarn),
+];
+
+impl PackageManager {
+    /// Picks the package manager from the lockfile in `project_dir`, defaulting to yarn.
+    pub fn detect(project_dir: &Path) -> Self {
+        LOCKFILES
+            .iter()
+            .find(|(lockfile, _)| project_dir.join(lockfile).exists())
+            .map(|(_, package_manager)| *package_manager)
+            .unwrap_or_default()
+    }
+
+    pub fn program(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    /// The arguments that run `script`, passing `args` through to it.
+    pub fn run_args(&self, script: &str, args: &[String]) -> Vec<String> {
+        let mut run_args = match self {
+            PackageManager::Yarn => vec![script.to_string()],
+            _ => vec!["run".to_string(), script.to_string()],
+        };
+
+        // npm would otherwise treat flags meant for the script as its own
+        if *self == PackageManager::Npm && !args.is_empty() {
+            run_args.push("--".to_string());
+        }
+
+        run_args.extend(args.iter().cloned());
+        run_args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::fs;
+
+    #[test]
+    fn it_detect_uses_lockfile() {
+        let dir = TestDir::new();
+
+        assert_eq!(PackageManager::detect(&dir), PackageManager::Yarn);
+
+        fs::write(dir.join("pnpm-lock.yaml"), "").unwrap();
+
+        assert_eq!(PackageManager::detect(&dir), PackageManager::Pnpm);
+    }
+
+    #[test]
+    fn it_run_args_passes_args_through() {
+        let args = vec!["--watch".to_string()];
+
+        assert_eq!(
+            PackageManager::Yarn.run_args("test", &args),
+            ["test", "--watch"]
+        );
+        assert_eq!(
+            PackageManager::Npm.run_args("test", &args),
+            ["run", "test", "--", "--watch"]
+        );
+        assert_eq!(PackageManager::Npm.run_args("test", &[]), ["run", "test"]);
+        assert_eq!(
+            PackageManager::Pnpm.run_args("test", &args),
+            ["run", "test", "--watch"]
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use crate::runner::{
+    env::{assignment, RunEnv},
+    package_manager::PackageManager,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RunRequest {
+    pub(crate) script: String,
+    #[serde(default)]
+    pub(crate) args: Vec<String>,
+    pub(crate) cwd: PathBuf,
+    #[serde(default)]
+    pub(crate) package_manager: PackageManager,
+    #[serde(default, skip_serializing_if = "RunEnv::is_empty")]
+    pub(crate) env: RunEnv,
+}
+
+impl RunRequest {
+    pub fn new(
+        script: String,
+        args: Vec<String>,
+        cwd: PathBuf,
+        package_manager: PackageManager,
+    ) -> Self {
+        Self {
+            script,
+            args,
+            cwd,
+            package_manager,
+            env: RunEnv::default(),
+        }
+    }
+
+    pub fn with_env(mut self, env: RunEnv) -> Self {
+        self.env = env;
+        self
+    }
+
+    pub fn script(&self) -> &str {
+        &self.script
+    }
+
+    pub fn cwd(&self) -> &Path {
+        &self.cwd
+    }
+
+    pub fn env(&self) -> &RunEnv {
+        &self.env
+    }
+
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(self.package_manager.program());
+        command
+            .args(self.package_manager.run_args(&self.script, &self.args))
+            .current_dir(&self.cwd)
+            .envs(self.env.resolve(&self.cwd))
+            .stdout(Stdio::inherit());
+
+        command
+    }
+
+    /// The command as it would be typed into a shell, quoted where needed. The variables it runs
+    /// with come first as assignments, including those read from env files.
+    pub fn command_line(&self) -> String {
+        self.env
+            .resolve(&self.cwd)
+            .iter()
+            .map(|(key, value)| assignment(key, value))
+            .chain(std::iter::once(self.quoted_command()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The command as `command_line` quotes it, without any environment.
+    pub(crate) fn quoted_command(&self) -> String {
+        let command = self.command();
+        let program = command.get_program().to_string_lossy();
+        let args = command.get_args().map(|arg| arg.to_string_lossy());
+
+        shell_words::join(std::iter::once(program).chain(args))
+    }
+
+    pub fn run(&self) -> std::io::Result<ExitStatus> {
+        self.command().status()
+    }
+
+    /// Starts the script without waiting for it, in its own process group on unix so that
+    /// everything it spawns can be stopped together. The group is made the terminal's foreground
+    /// group, so the script can still read input, until `job_control::take_terminal` takes it
+    /// back.
+    #[cfg(feature = "process")]
+    pub fn spawn(&self) -> std::io::Result<std::process::Child> {
+        let mut command = self.command();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+
+            command.process_group(0);
+            // SAFETY: `give_terminal` only calls async-signal-safe functions
+            unsafe {
+                command.pre_exec(|| {
+                    crate::runner::job_control::give_terminal(libc::getpid());
+                    Ok(())
+                });
+            }
+        }
+
+        command.spawn()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::fs;
+
+    #[test]
+    fn it_command_passes_script_args_and_cwd() {
+        let request = RunRequest::new(
+            "test".to_string(),
+            vec!["--watch".to_string()],
+            PathBuf::from("/tmp"),
+            PackageManager::Yarn,
+        );
+
+        let command = request.command();
+        let args: Vec<_> = command.get_args().collect();
+
+        assert_eq!(command.get_program(), "yarn");
+        assert_eq!(args, vec!["test", "--watch"]);
+        assert_eq!(
+            command.get_current_dir(),
+            Some(PathBuf::from("/tmp").as_path())
+        );
+    }
+
+    #[test]
+    fn it_command_line_quotes_args() {
+        let request = RunRequest::new(
+            "test".to_string(),
+            vec!["--grep".to_string(), "it's slow".to_string()],
+            PathBuf::from("/tmp"),
+            PackageManager::Npm,
+        );
+
+        assert_eq!(
+            request.command_line(),
+            r#"npm run test -- --grep 'it'\''s slow'"#
+        );
+    }
+
+    #[test]
+    fn it_command_sets_env() {
+        let request = RunRequest::new(
+            "test".to_string(),
+            vec![],
+            PathBuf::from("/tmp"),
+            PackageManager::Npm,
+        )
+        .with_env(RunEnv::parse("DEBUG=1 MESSAGE='a b'").unwrap());
+
+        let command = request.command();
+        let envs: Vec<_> = command.get_envs().collect();
+
+        assert!(envs.contains(&("DEBUG".as_ref(), Some("1".as_ref()))));
+        assert_eq!(request.command_line(), "DEBUG=1 MESSAGE='a b' npm run test");
+    }
+
+    #[test]
+    fn it_command_line_includes_variables_from_env_files() {
+        let dir = TestDir::new();
+        fs::write(dir.join(".env"), "API_URL=http://localhost\nDEBUG=0\n").unwrap();
+
+        let request = RunRequest::new(
+            "test".to_string(),
+            vec![],
+            dir.to_path_buf(),
+            PackageManager::Npm,
+        )
+        .with_env(RunEnv::dotenv(None).merge(RunEnv::parse("DEBUG=1").unwrap()));
+
+        assert_eq!(
+            request.command_line(),
+            "API_URL=http://localhost DEBUG=1 npm run test"
+        );
+    }
+
+    #[test]
+    fn it_command_uses_package_manager() {
+        let request = RunRequest::new(
+            "build".to_string(),
+            vec![],
+            PathBuf::from("/tmp"),
+            PackageManager::Npm,
+        );
+
+        let command = request.command();
+        let args: Vec<_> = command.get_args().collect();
+
+        assert_eq!(command.get_program(), "npm");
+        assert_eq!(args, vec!["run", "build"]);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::runner::run_request::RunRequest;
+use color_eyre::{eyre::WrapErr, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    WalkBuilder,
+};
+use notify::{RecursiveMode, Watcher};
+use std::{
+    path::{Path, PathBuf},
+    process::{Child, ExitStatus},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const DEBOUNCE: Duration = Duration::from_millis(300);
+const STOP_TIMEOUT: Duration = Duration::from_secs(3);
+// How often to check whether the script exited or the watch was interrupted while waiting for
+// changes
+const EXIT_CHECK: Duration = Duration::from_millis(100);
+
+// Directories that are never watched, even without a .gitignore listing them
+const SKIPPED_DIRS: [&str; 2] = [".git", "node_modules"];
+
+pub struct FileFilter {
+    root: PathBuf,
+    globs: GlobSet,
+    gitignore: Gitignore,
+}
+
+impl FileFilter {
+    pub fn new(root: &Path, patterns: &[String]) -> Result<Self> {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+
+        let mut globs = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob =
+                Glob::new(pattern).wrap_err_with(|| format!("Invalid watch glob `{pattern}`"))?;
+            globs.add(glob);
+        }
+
+        let mut gitignore = GitignoreBuilder::new(&root);
+        gitignore.add(root.join(".gitignore"));
+
+        Ok(Self {
+            globs: globs.build()?,
+            gitignore: gitignore.build()?,
+            root,
+        })
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        !self.is_ignored(path)
+            && path
+                .strip_prefix(&self.root)
+                .is_ok_and(|relative| self.globs.is_match(relative))
+    }
+
+    /// Whether changes to `path` are never relevant: it's outside the root, in a skipped
+    /// directory or gitignored.
+    fn is_ignored(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return true;
+        };
+
+        if SKIPPED_DIRS.iter().any(|dir| relative.starts_with(dir)) {
+            return true;
+        }
+
+        self.gitignore
+            .matched_path_or_any_parents(relative, path.is_dir())
+            .is_ignore()
+    }
+}
+
+/// The directories under `root` (including itself) to watch, leaving out ignored ones like
+/// `node_modules` so they aren't each registered with the OS.
+pub fn watched_dirs(root: &Path) -> Vec<PathBuf> {
+    WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| {
+            entry.file_type().is_some_and(|kind| kind.is_dir())
+                && !SKIPPED_DIRS.iter().any(|dir| entry.file_name() == *dir)
+        })
+        .build()
+        .filter_map(|entry| Some(entry.ok()?.into_path()))
+        .collect()
+}
+
+fn watch_dirs(watcher: &mut impl Watcher, root: &Path) -> notify::Result<()> {
+    for dir in watched_dirs(root) {
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+    }
+
+    Ok(())
+}
+
+/// Runs the script and restarts it whenever a file matching one of `patterns` changes, until
+/// the script is interrupted or `interrupted` is set, e.g. from the caller's ctrl-c handler.
+pub fn watch(request: &RunRequest, patterns: &[String], interrupted: &AtomicBool) -> Result<()> {
+    let filter = FileFilter::new(&request.cwd, patterns)?;
+    let (sender, receiver) = mpsc::channel();
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if !event.kind.is_access() {
+                let _ = sender.send(event.paths);
+            }
+        }
+    })?;
+    watch_dirs(&mut watcher, &filter.root)
+        .wrap_err_with(|| format!("Could not watch {}", request.cwd.display()))?;
+
+    let mut child = request.spawn()?;
+    let mut exited = false;
+
+    loop {
+        if interrupted.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let paths = match receiver.recv_timeout(EXIT_CHECK) {
+            Ok(paths) => paths,
+            Err(RecvTimeoutError::Timeout) => {
+                // While the script has the terminal, ctrl-c only reaches the script, so its exit
+                // is how scriptor learns of it
+                if !exited {
+                    if let Some(status) = child.try_wait()? {
+                        exited = true;
+                        take_terminal();
+
+                        if is_interrupt(status) {
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        // Directories created since the watch started need watches of their own
+        for path in &paths {
+            if path.is_dir() && !filter.is_ignored(path) {
+                let _ = watch_dirs(&mut watcher, path);
+            }
+        }
+
+        if !paths.iter().any(|path| filter.matches(path)) {
+            continue;
+        }
+
+        if wait_for_quiet(&receiver, &filter, interrupted) {
+            break;
+        }
+
+        take_terminal();
+        stop(&mut child)?;
+        eprintln!("[scriptor] Change detected, restarting {}", request.script);
+        child = request.spawn()?;
+        exited = false;
+    }
+
+    take_terminal();
+    stop(&mut child)?;
+
+    Ok(())
+}
+
+/// Swallows relevant changes until none have arrived for `DEBOUNCE`, so a save touching several
+/// files only restarts once. Returns true if the watch was interrupted while waiting.
+fn wait_for_quiet(
+    receiver: &Receiver<Vec<PathBuf>>,
+    filter: &FileFilter,
+    interrupted: &AtomicBool,
+) -> bool {
+    let mut deadline = Instant::now() + DEBOUNCE;
+
+    loop {
+        match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(paths) => {
+                if paths.iter().any(|path| filter.matches(path)) {
+                    deadline = Instant::now() + DEBOUNCE;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return true,
+            Err(RecvTimeoutError::Timeout) => return interrupted.load(Ordering::SeqCst),
+        }
+    }
+}
+
+fn stop(child: &mut Child) -> std::io::Result<()> {
+    if child.try_wait()?.is_some() {
+        return Ok(());
+    }
+
+    terminate(child)?;
+
+    let started = Instant::now();
+    while started.elapsed() < STOP_TIMEOUT {
+        if child.try_wait()?.is_some() {
+            return Ok(());
+        }
+
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    child.kill()?;
+    child.wait()?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+use crate::runner::job_control::take_terminal;
+
+#[cfg(not(unix))]
+fn take_terminal() {}
+
+/// Whether the script exited because of ctrl-c, which package managers pass on as exit code 130.
+#[cfg(unix)]
+fn is_interrupt(status: ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+
+    status.signal() == Some(libc::SIGINT) || status.code() == Some(130)
+}
+
+#[cfg(not(unix))]
+fn is_interrupt(_status: ExitStatus) -> bool {
+    // ctrl-c reaches scriptor's handler directly outside unix
+    false
+}
+
+#[cfg(unix)]
+fn terminate(child: &mut Child) -> std::io::Result<()> {
+    // The child leads its own process group (see `RunRequest::spawn`), so signal the whole group
+    // to take down anything the package manager started as well.
+    let result = unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGTERM) };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+fn terminate(child: &mut Child) -> std::io::Result<()> {
+    child.kill()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use std::fs;
+
+    fn project_dir() -> TestDir {
+        let dir = TestDir::new();
+        fs::write(dir.join(".gitignore"), "dist/\n*.log\n").unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn it_matches_files_matching_glob() {
+        let dir = project_dir();
+        let filter = FileFilter::new(&dir, &["src/**/*.ts".to_string()]).unwrap();
+
+        assert!(filter.matches(&dir.join("src/components/app.ts")));
+        assert!(!filter.matches(&dir.join("src/components/app.js")));
+        assert!(!filter.matches(&dir.join("test/app.ts")));
+    }
+
+    #[test]
+    fn it_does_not_match_gitignored_files() {
+        let dir = project_dir();
+        let filter = FileFilter::new(&dir, &["**/*".to_string()]).unwrap();
+
+        assert!(filter.matches(&dir.join("src/index.js")));
+        assert!(!filter.matches(&dir.join("dist/index.js")));
+        assert!(!filter.matches(&dir.join("debug.log")));
+        assert!(!filter.matches(&dir.join(".git/index")));
+        assert!(!filter.matches(&dir.join("node_modules/react/index.js")));
+    }
+
+    #[test]
+    fn it_watched_dirs_skips_ignored_dirs() {
+        let dir = project_dir();
+        for sub in [
+            "src/nested",
+            "dist/assets",
+            "node_modules/react",
+            ".git/objects",
+        ] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+
+        let mut dirs = watched_dirs(&dir);
+        dirs.sort();
+
+        assert_eq!(
+            dirs,
+            vec![dir.to_path_buf(), dir.join("src"), dir.join("src/nested")]
+        );
+    }
+
+    #[test]
+    fn it_does_not_match_files_outside_root() {
+        let dir = project_dir();
+        let filter = FileFilter::new(&dir, &["**/*".to_string()]).unwrap();
+
+        assert!(!filter.matches(Path::new("/somewhere/else.ts")));
+    }
+
+    #[test]
+    fn it_rejects_invalid_globs() {
+        let dir = project_dir();
+
+        assert!(FileFilter::new(&dir, &["src/[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn it_wait_for_quiet_stops_when_interrupted() {
+        let dir = project_dir();
+        let filter = FileFilter::new(&dir, &["src/**/*.ts".to_string()]).unwrap();
+        let (sender, receiver) = mpsc::channel();
+        sender.send(vec![dir.join("src/app.ts")]).unwrap();
+
+        assert!(wait_for_quiet(&receiver, &filter, &AtomicBool::new(true)));
+        assert!(!wait_for_quiet(&receiver, &filter, &AtomicBool::new(false)));
+    }
+}
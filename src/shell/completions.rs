@@ -0,0 +1,236 @@
+use crate::cli::Cli;
+use clap::CommandFactory;
+use clap_complete::{generate, Shell};
+
+const BASH_SCRIPTS: &str = r#"
+_scriptor_scripts() {
+    _scriptor "$@"
+    local cur="${COMP_WORDS[COMP_CWORD]}" cwd=() positional=() i word
+    for (( i = 1; i < COMP_CWORD; i++ )); do
+        word="${COMP_WORDS[i]}"
+        case "${word}" in
+            --cwd|--watch|--env|--env-mode)
+                # bash splits `--cwd=dir` into `--cwd`, `=` and `dir`
+                [[ ${COMP_WORDS[i + 1]} == = ]] && (( i++ ))
+                (( i++ ))
+                [[ ${word} == --cwd ]] && cwd=(--cwd "${COMP_WORDS[i]/#\~/$HOME}")
+                ;;
+            --cwd=*) cwd=(--cwd "${word#--cwd=}") ;;
+            # The rest of a split value, as in `--env KEY=value`
+            =) (( i++ )) ;;
+            -*) ;;
+            *) positional+=("${word}") ;;
+        esac
+    done
+    if [[ ${cur} != -* ]] && [[ ${#positional[@]} -eq 0 || ( ${#positional[@]} -eq 1 && ${positional[0]} == run ) ]]; then
+        COMPREPLY+=( $(compgen -W "$(scriptor "${cwd[@]}" --complete 2>/dev/null)" -- "${cur}") )
+    fi
+}
+
+complete -F _scriptor_scripts -o nosort -o bashdefault -o default scriptor
+"#;
+
+const ZSH_SCRIPTS: &str = r#"(( $+functions[_scriptor_scripts] )) ||
+_scriptor_scripts() {
+    local -a scripts cwd typed
+    local i
+    typed=(${(Q)${(z)LBUFFER}})
+    for (( i = 1; i < ${#typed}; i++ )); do
+        case $typed[i] in
+            --cwd) cwd=(--cwd "${typed[i + 1]/#\~/$HOME}") ;;
+            --cwd=*) cwd=(--cwd "${${typed[i]#--cwd=}/#\~/$HOME}") ;;
+        esac
+    done
+    scripts=(${(f)"$(scriptor $cwd --complete 2>/dev/null)"})
+    compadd -a scripts
+}
+
+"#;
+
+const FISH_SCRIPTS: &str = r#"
+function __fish_scriptor_scripts
+    set -l typed (commandline -opc)
+    set -l cwd
+    for i in (seq (count $typed))
+        switch $typed[$i]
+            case --cwd
+                set cwd --cwd (string replace -r '^~' $HOME -- $typed[(math $i + 1)])
+            case '--cwd=*'
+                set cwd --cwd (string replace -r '^--cwd=' '' -- $typed[$i] | string replace -r '^~' $HOME)
+        end
+    end
+    scriptor $cwd --complete 2>/dev/null
+end
+
+complete -c scriptor -n "__fish_scriptor_needs_command" -f -a "(__fish_scriptor_scripts)" -d Script
+complete -c scriptor -n "__fish_scriptor_using_subcommand run" -f -a "(__fish_scriptor_scripts)" -d Script
+"#;
+
+const POWERSHELL_SCRIPTS: &str = r#"            $typed = @($commandElements | ForEach-Object { "$_" })
+            $cwd = @($typed -like '--cwd=*')
+            $index = [array]::IndexOf($typed, '--cwd')
+            if ($index -ge 0 -and $index + 1 -lt $typed.Count) {
+                $cwd = '--cwd', $typed[$index + 1]
+            }
+            scriptor @cwd --complete 2>$null | ForEach-Object {
+                [CompletionResult]::new($_, $_, [CompletionResultType]::ParameterValue, 'Script')
+            }
+"#;
+
+/// Completions for the shell, which also complete script names by calling `scriptor --complete`
+/// so they follow the project the shell is in, or the one `--cwd` names, both for the script
+/// argument and `run <script>`.
+/// Elvish only gets the static completions.
+pub fn completions(shell: Shell) -> String {
+    let mut command = Cli::command();
+    let mut script = vec![];
+    generate(shell, &mut command, "scriptor", &mut script);
+    let script = String::from_utf8_lossy(&script).into_owned();
+
+    match shell {
+        Shell::Bash => {
+            expect(&script, "_scriptor() {");
+            script + BASH_SCRIPTS
+        }
+        Shell::Zsh => {
+            let script_help = command
+                .get_arguments()
+                .find(|arg| arg.get_id() == "script")
+                .and_then(|arg| arg.get_help())
+                .map(ToString::to_string)
+                .unwrap_or_default();
+
+            let script = patch(
+                script,
+                &format!("'::script -- {script_help}:_default'"),
+                &format!("'::script -- {script_help}:_scriptor_scripts'"),
+            );
+            let script = patch(script, "':script:_default'", "':script:_scriptor_scripts'");
+            patch(
+                script,
+                "if [ \"$funcstack[1]\" = \"_scriptor\" ]",
+                &format!("{ZSH_SCRIPTS}if [ \"$funcstack[1]\" = \"_scriptor\" ]"),
+            )
+        }
+        Shell::Fish => {
+            expect(&script, "function __fish_scriptor_needs_command");
+            expect(&script, "function __fish_scriptor_using_subcommand");
+            script + FISH_SCRIPTS
+        }
+        Shell::PowerShell => {
+            let script = patch(
+                script,
+                "        'scriptor' {\n",
+                &format!("        'scriptor' {{\n{POWERSHELL_SCRIPTS}"),
+            );
+            patch(
+                script,
+                "        'scriptor;run' {\n",
+                &format!("        'scriptor;run' {{\n{POWERSHELL_SCRIPTS}"),
+            )
+        }
+        _ => script,
+    }
+}
+
+/// Replaces `from` in clap_complete's output, which must contain it once.
+fn patch(script: String, from: &str, to: &str) -> String {
+    expect(&script, from);
+    script.replacen(from, to, 1)
+}
+
+/// Panics unless clap_complete's output contains `part` exactly once, so a clap_complete update
+/// can't quietly turn script name completion off.
+fn expect(script: &str, part: &str) {
+    assert_eq!(
+        script.matches(part).count(),
+        1,
+        "expected `{part}` once in the generated completions"
+    );
+}
+
+/// Script names for `--complete`, one per line.
+pub fn script_names(scripts: impl IntoIterator<Item = String>) -> String {
+    let mut names: Vec<String> = scripts.into_iter().collect();
+    names.sort();
+
+    names.into_iter().map(|name| name + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_completes_script_names_in_every_shell() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let script = completions(shell);
+
+            assert!(
+                script.contains(" --complete 2>"),
+                "{shell} completions should complete script names"
+            );
+        }
+    }
+
+    #[test]
+    fn it_forwards_cwd_when_completing_script_names() {
+        let cases = [
+            (Shell::Bash, r#"scriptor "${cwd[@]}" --complete"#),
+            (Shell::Zsh, "scriptor $cwd --complete"),
+            (Shell::Fish, "scriptor $cwd --complete"),
+            (Shell::PowerShell, "scriptor @cwd --complete"),
+        ];
+
+        for (shell, call) in cases {
+            let script = completions(shell);
+
+            assert!(
+                script.contains("--cwd"),
+                "{shell} completions should find --cwd"
+            );
+            assert!(
+                script.contains(call),
+                "{shell} completions should forward --cwd"
+            );
+        }
+    }
+
+    #[test]
+    fn it_completes_script_names_after_run() {
+        let cases = [
+            (Shell::Bash, "${positional[0]} == run"),
+            (
+                Shell::Fish,
+                "using_subcommand run\" -f -a \"(__fish_scriptor_scripts)",
+            ),
+            (Shell::PowerShell, "'scriptor;run' {\n            $typed ="),
+        ];
+
+        for (shell, completion) in cases {
+            assert!(
+                completions(shell).contains(completion),
+                "{shell} completions should complete `run <script>`"
+            );
+        }
+    }
+
+    #[test]
+    fn it_zsh_uses_script_completer_for_script_argument() {
+        let script = completions(Shell::Zsh);
+
+        assert!(script.contains("instead of picking one:_scriptor_scripts'"));
+        assert!(script.contains("':script:_scriptor_scripts'"));
+        assert!(
+            script.find("_scriptor_scripts() {").unwrap()
+                < script.find("if [ \"$funcstack[1]\"").unwrap()
+        );
+    }
+
+    #[test]
+    fn it_script_names_are_sorted_lines() {
+        let names = script_names(["test".to_string(), "build".to_string()]);
+
+        assert_eq!(names, "build\ntest\n");
+    }
+}
@@ -0,0 +1,10 @@
+# scriptor: press alt-s to pick a script and insert its command at the cursor
+__scriptor_widget() {
+  local command
+  command="$(scriptor --print)" || return
+  READLINE_LINE="${READLINE_LINE:0:$READLINE_POINT}${command}${READLINE_LINE:$READLINE_POINT}"
+  READLINE_POINT=$((READLINE_POINT + ${#command}))
+}
+
+bind -m emacs-standard -x '"\es": __scriptor_widget'
+bind -m vi-insert -x '"\es": __scriptor_widget'
@@ -0,0 +1,9 @@
+# scriptor: press alt-s to pick a script and insert its command at the cursor
+function scriptor-widget
+    set -l command (scriptor --print)
+    and commandline --insert -- $command
+    commandline --function repaint
+end
+
+bind \es scriptor-widget
+bind -M insert \es scriptor-widget
@@ -0,0 +1,30 @@
+use clap::ValueEnum;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// The snippet to add to the shell's startup file, binding alt-s to a widget that inserts the
+/// command printed by `scriptor --print` at the cursor.
+pub fn init_script(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash => include_str!("init.bash"),
+        Shell::Zsh => include_str!("init.zsh"),
+        Shell::Fish => include_str!("init.fish"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_init_script_calls_print_for_every_shell() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            assert!(init_script(shell).contains("scriptor --print"));
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod project_store;
+pub mod run_log;
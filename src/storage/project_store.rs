@@ -0,0 +1,283 @@
+use crate::runner::{env::RunEnv, run_request::RunRequest};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+pub(crate) struct ProjectData {
+    #[serde(default)]
+    pub pinned: Vec<String>,
+    #[serde(default)]
+    pub history: Vec<RunRequest>,
+    #[serde(default)]
+    pub queries: Vec<String>,
+    /// The environment each script runs with unless another is picked for a run.
+    #[serde(default)]
+    pub env_presets: HashMap<String, RunEnv>,
+}
+
+const HISTORY_LIMIT: usize = 50;
+
+pub struct ProjectStore {
+    path: PathBuf,
+}
+
+impl ProjectStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn for_project(project_dir: &Path) -> Self {
+        let file_name = format!("{}.json", project_key(project_dir));
+
+        Self::new(data_dir().join("projects").join(file_name))
+    }
+
+    pub(crate) fn load(&self) -> ProjectData {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn save(&self, data: &ProjectData) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&self.path, serde_json::to_string_pretty(data)?)
+    }
+
+    /// The pinned scripts, in the order they were pinned.
+    pub fn pinned(&self) -> Vec<String> {
+        self.load().pinned
+    }
+
+    pub fn save_pinned(&self, pinned: Vec<String>) -> std::io::Result<()> {
+        let mut data = self.load();
+        data.pinned = pinned;
+
+        self.save(&data)
+    }
+
+    /// Past search queries, oldest first.
+    pub fn queries(&self) -> Vec<String> {
+        self.load().queries
+    }
+
+    pub fn last_run(&self) -> Option<RunRequest> {
+        self.load().history.pop()
+    }
+
+    /// Adds a run to the history. Only the env files it loaded are kept, not the variables set
+    /// for it, as those can hold secrets.
+    pub fn record_run(&self, request: &RunRequest) -> std::io::Result<()> {
+        let mut data = self.load();
+        data.history
+            .push(request.clone().with_env(request.env.files_only()));
+
+        let overflow = data.history.len().saturating_sub(HISTORY_LIMIT);
+        data.history.drain(..overflow);
+
+        self.save(&data)
+    }
+
+    /// Remembers a search query, moving it to the end if it was used before.
+    pub fn record_query(&self, query: &str) -> std::io::Result<()> {
+        let mut data = self.load();
+        data.queries.retain(|previous| previous != query);
+        data.queries.push(query.to_string());
+
+        let overflow = data.queries.len().saturating_sub(HISTORY_LIMIT);
+        data.queries.drain(..overflow);
+
+        self.save(&data)
+    }
+
+    pub fn env_preset(&self, script: &str) -> Option<RunEnv> {
+        self.load().env_presets.remove(script)
+    }
+
+    /// Saves the environment `script` runs with, forgetting it when `env` is empty.
+    pub fn save_env_preset(&self, script: &str, env: RunEnv) -> std::io::Result<()> {
+        let mut data = self.load();
+
+        if env.is_empty() {
+            data.env_presets.remove(script);
+        } else {
+            data.env_presets.insert(script.to_string(), env);
+        }
+
+        self.save(&data)
+    }
+
+    /// Scripts from the run history, most recently run first.
+    pub fn recent_scripts(&self) -> Vec<String> {
+        let mut recent: Vec<String> = vec![];
+
+        for request in self.load().history.into_iter().rev() {
+            if !recent.contains(&request.script) {
+                recent.push(request.script);
+            }
+        }
+
+        recent
+    }
+}
+
+/// Returns the directory scriptor keeps its state in.
+///
+/// `SCRIPTOR_DATA_DIR` takes precedence so the location can be moved (or isolated in tests),
+/// falling back to the platform data directory.
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("SCRIPTOR_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from(".scriptor"))
+        .join("scriptor")
+}
+
+/// A file name for the project's state. Bytes other than letters, digits, `-` and `.` are
+/// written as `_` and their hex code, so no two projects share one.
+pub(crate) fn project_key(project_dir: &Path) -> String {
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+
+    project_dir
+        .as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .map(|&byte| {
+            if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'.' {
+                (byte as char).to_string()
+            } else {
+                format!("_{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::package_manager::PackageManager;
+    use crate::test_dir::TestDir;
+
+    fn temp_store() -> (TestDir, ProjectStore) {
+        let dir = TestDir::new();
+        let store = ProjectStore::new(dir.join("project.json"));
+
+        (dir, store)
+    }
+
+    #[test]
+    fn it_load_returns_default_when_file_missing() {
+        let (_dir, store) = temp_store();
+
+        assert_eq!(store.load(), ProjectData::default());
+    }
+
+    #[test]
+    fn it_save_then_load_round_trips() {
+        let (_dir, store) = temp_store();
+        let data = ProjectData {
+            pinned: vec!["build".to_string(), "test".to_string()],
+            history: vec![],
+            queries: vec!["build".to_string()],
+            env_presets: HashMap::from([("test".to_string(), RunEnv::dotenv(None))]),
+        };
+
+        store.save(&data).unwrap();
+
+        assert_eq!(store.load(), data);
+    }
+
+    #[test]
+    fn it_record_run_keeps_most_recent_runs() {
+        let (_dir, store) = temp_store();
+
+        for i in 0..HISTORY_LIMIT + 5 {
+            let request = RunRequest::new(
+                format!("script-{i}"),
+                vec![],
+                PathBuf::from("/"),
+                PackageManager::Yarn,
+            );
+            store.record_run(&request).unwrap();
+        }
+
+        let history = store.load().history;
+        let last = store.last_run().unwrap();
+
+        assert_eq!(history.len(), HISTORY_LIMIT);
+        assert_eq!(history[0].script, "script-5");
+        assert_eq!(last.script, format!("script-{}", HISTORY_LIMIT + 4));
+    }
+
+    #[test]
+    fn it_record_run_leaves_out_env_variables() {
+        let (_dir, store) = temp_store();
+        let request = RunRequest::new(
+            "deploy".to_string(),
+            vec![],
+            PathBuf::from("/"),
+            PackageManager::Npm,
+        )
+        .with_env(RunEnv::parse(".env.prod TOKEN=secret").unwrap());
+
+        store.record_run(&request).unwrap();
+
+        assert_eq!(
+            store.last_run().unwrap().env,
+            RunEnv::parse(".env.prod").unwrap()
+        );
+    }
+
+    #[test]
+    fn it_record_query_moves_repeated_queries_to_end() {
+        let (_dir, store) = temp_store();
+
+        for query in ["build", "test", "build"] {
+            store.record_query(query).unwrap();
+        }
+
+        assert_eq!(store.load().queries, vec!["test", "build"]);
+    }
+
+    #[test]
+    fn it_save_env_preset_replaces_and_clears_preset() {
+        let (_dir, store) = temp_store();
+        let env = RunEnv::parse("DEBUG=1").unwrap();
+
+        store.save_env_preset("test", env.clone()).unwrap();
+        assert_eq!(store.env_preset("test"), Some(env));
+
+        store.save_env_preset("test", RunEnv::default()).unwrap();
+        assert_eq!(store.env_preset("test"), None);
+    }
+
+    #[test]
+    fn it_project_key_replaces_path_separators() {
+        let key = project_key(Path::new("/not/a/real dir"));
+
+        assert_eq!(key, "_2Fnot_2Fa_2Freal_20dir");
+    }
+
+    #[test]
+    fn it_project_key_keeps_similar_paths_apart() {
+        let keys: Vec<_> = ["/a/my_app", "/a/my/app", "/a/my app", "/a/my_2Fapp"]
+            .into_iter()
+            .map(|dir| project_key(Path::new(dir)))
+            .collect();
+
+        for (index, key) in keys.iter().enumerate() {
+            assert!(!keys[index + 1..].contains(key), "{key} is shared");
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use crate::storage::project_store::{data_dir, project_key};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const LOG_LIMIT: usize = 100;
+
+/// What a captured run did, saved as `<id>.json` next to its output in `<id>.log`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RunLog {
+    id: String,
+    script: String,
+    command: String,
+    /// When the run started, in milliseconds since the unix epoch.
+    started: u64,
+    duration_ms: u64,
+    /// The script's exit code, which is missing when it was killed by a signal.
+    exit_code: Option<i32>,
+}
+
+impl RunLog {
+    pub fn new(script: String, command: String, started: SystemTime) -> Self {
+        let started = started
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        Self {
+            // Runs in other scriptor processes can start in the same millisecond
+            id: format!("{started}-{}", std::process::id()),
+            script,
+            command,
+            started,
+            duration_ms: 0,
+            exit_code: None,
+        }
+    }
+
+    /// Records how long the run took and how it exited.
+    pub fn finish(&mut self, duration: Duration, exit_code: Option<i32>) {
+        self.duration_ms = duration.as_millis() as u64;
+        self.exit_code = exit_code;
+    }
+
+    pub fn script(&self) -> &str {
+        &self.script
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// The script's exit code, which is missing when it was killed by a signal.
+    pub fn exit_code(&self) -> Option<i32> {
+        self.exit_code
+    }
+
+    /// The start time as `YYYY-MM-DD HH:MM:SS` in UTC.
+    pub fn started_at(&self) -> String {
+        let seconds = self.started / 1000;
+        let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+        let time = seconds % 86_400;
+
+        format!(
+            "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+            time / 3600,
+            time / 60 % 60,
+            time % 60
+        )
+    }
+
+    /// The duration, like `850ms`, `12.3s` or `4m05s`.
+    pub fn duration(&self) -> String {
+        let ms = self.duration_ms;
+
+        match ms {
+            0..1_000 => format!("{ms}ms"),
+            1_000..60_000 => format!("{:.1}s", ms as f64 / 1000.0),
+            _ => format!("{}m{:02}s", ms / 60_000, ms / 1000 % 60),
+        }
+    }
+}
+
+/// Which stream a line of output was written to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stream {
+    Out,
+    Err,
+}
+
+/// A line of a log file: the time since the run started, the stream and the text, e.g.
+/// `00:01.250 err Cannot find module`.
+#[cfg(any(test, feature = "process"))]
+pub(crate) fn log_line(elapsed: Duration, stream: Stream, text: &str) -> String {
+    let ms = elapsed.as_millis();
+    let stream = match stream {
+        Stream::Out => "out",
+        Stream::Err => "err",
+    };
+
+    format!(
+        "{:02}:{:02}.{:03} {stream} {text}\n",
+        ms / 60_000,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+/// A line of a run's captured output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputLine {
+    elapsed: String,
+    stream: Stream,
+    text: String,
+}
+
+impl OutputLine {
+    /// Reads a line `log_line` wrote. Any other line is taken as stdout at no particular time.
+    fn parse(line: &str) -> Self {
+        // The minutes grow past two digits in long runs, so the fields can't be at fixed offsets
+        let mut fields = line.splitn(3, ' ');
+        let (elapsed, stream, text) = (fields.next(), fields.next(), fields.next());
+        let stream = match stream {
+            Some("out") => Some(Stream::Out),
+            Some("err") => Some(Stream::Err),
+            _ => None,
+        };
+
+        match (elapsed, stream) {
+            (Some(elapsed), Some(stream)) if is_elapsed(elapsed) => Self {
+                elapsed: elapsed.to_string(),
+                stream,
+                text: text.unwrap_or_default().to_string(),
+            },
+            _ => Self {
+                elapsed: String::new(),
+                stream: Stream::Out,
+                text: line.to_string(),
+            },
+        }
+    }
+
+    /// The time since the run started, like `01:01.250`, or empty if it isn't known.
+    pub fn elapsed(&self) -> &str {
+        &self.elapsed
+    }
+
+    pub fn stream(&self) -> Stream {
+        self.stream
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Whether `field` is a time like `log_line` writes, e.g. `01:01.250`.
+fn is_elapsed(field: &str) -> bool {
+    field.contains(':')
+        && field
+            .chars()
+            .all(|char| char.is_ascii_digit() || ":.".contains(char))
+}
+
+/// The captured runs of one project, kept in a directory of their own.
+#[derive(Clone)]
+pub struct RunLogs {
+    dir: PathBuf,
+}
+
+impl RunLogs {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn for_project(project_dir: &Path) -> Self {
+        Self::new(data_dir().join("logs").join(project_key(project_dir)))
+    }
+
+    /// Every saved run, most recent first.
+    pub fn list(&self) -> Vec<RunLog> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return vec![];
+        };
+
+        let mut logs: Vec<RunLog> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+
+                serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+            })
+            .collect();
+
+        logs.sort_by_key(|log| Reverse(log.started));
+        logs
+    }
+
+    pub fn output_path(&self, log: &RunLog) -> PathBuf {
+        self.dir.join(format!("{}.log", log.id))
+    }
+
+    /// The captured output of a run, empty if there was none.
+    pub fn output(&self, log: &RunLog) -> Vec<OutputLine> {
+        fs::read_to_string(self.output_path(log))
+            .unwrap_or_default()
+            .lines()
+            .map(OutputLine::parse)
+            .collect()
+    }
+
+    /// Creates the directory output is written to before a run starts.
+    pub fn create_dir(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)
+    }
+
+    /// Saves a finished run, removing the oldest runs beyond the limit.
+    pub fn save(&self, log: &RunLog) -> io::Result<()> {
+        self.create_dir()?;
+        fs::write(
+            self.dir.join(format!("{}.json", log.id)),
+            serde_json::to_string_pretty(log)?,
+        )?;
+
+        for old in self.list().iter().skip(LOG_LIMIT) {
+            let _ = fs::remove_file(self.dir.join(format!("{}.json", old.id)));
+            let _ = fs::remove_file(self.output_path(old));
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts days since the unix epoch to a (year, month, day) date, after Howard Hinnant's
+/// `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    fn temp_logs() -> (TestDir, RunLogs) {
+        let dir = TestDir::new();
+        let logs = RunLogs::new(dir.join("logs"));
+
+        (dir, logs)
+    }
+
+    fn log_at(started: u64) -> RunLog {
+        RunLog {
+            id: started.to_string(),
+            script: "test".to_string(),
+            command: "npm run test".to_string(),
+            started,
+            duration_ms: 1500,
+            exit_code: Some(0),
+        }
+    }
+
+    #[test]
+    fn it_list_returns_most_recent_first() {
+        let (_dir, logs) = temp_logs();
+
+        for started in [2000, 3000, 1000] {
+            logs.save(&log_at(started)).unwrap();
+        }
+
+        let started: Vec<_> = logs.list().iter().map(|log| log.started).collect();
+        assert_eq!(started, vec![3000, 2000, 1000]);
+    }
+
+    #[test]
+    fn it_save_removes_oldest_runs_beyond_limit() {
+        let (_dir, logs) = temp_logs();
+
+        for started in 0..LOG_LIMIT as u64 + 3 {
+            let log = log_at(started);
+            logs.save(&log).unwrap();
+            fs::write(logs.output_path(&log), "output").unwrap();
+        }
+
+        let list = logs.list();
+        assert_eq!(list.len(), LOG_LIMIT);
+        assert_eq!(list.last().unwrap().started, 3);
+        assert!(!logs.output_path(&log_at(2)).exists());
+    }
+
+    #[test]
+    fn it_new_keeps_runs_from_other_processes_apart() {
+        let (_dir, logs) = temp_logs();
+        let log = RunLog::new(
+            "test".to_string(),
+            "npm run test".to_string(),
+            UNIX_EPOCH + Duration::from_millis(2000),
+        );
+
+        let name = format!("2000-{}.log", std::process::id());
+        assert_eq!(logs.output_path(&log).file_name().unwrap(), name.as_str());
+    }
+
+    #[test]
+    fn it_formats_start_time_and_duration() {
+        // 2024-02-29 13:05:09 UTC
+        let mut log = log_at(1_709_211_909_000);
+
+        assert_eq!(log.started_at(), "2024-02-29 13:05:09");
+        assert_eq!(log.duration(), "1.5s");
+
+        log.duration_ms = 245_000;
+        assert_eq!(log.duration(), "4m05s");
+    }
+
+    #[test]
+    fn it_log_line_records_elapsed_time_and_stream() {
+        let line = log_line(Duration::from_millis(61_250), Stream::Err, "failed");
+
+        assert_eq!(line, "01:01.250 err failed\n");
+
+        let line = OutputLine::parse(line.trim_end());
+        assert_eq!(line.elapsed(), "01:01.250");
+        assert_eq!(line.stream(), Stream::Err);
+        assert_eq!(line.text(), "failed");
+
+        let line = OutputLine::parse("plain text out there");
+        assert_eq!(
+            (line.elapsed(), line.stream(), line.text()),
+            ("", Stream::Out, "plain text out there")
+        );
+    }
+
+    #[test]
+    fn it_reads_lines_logged_after_100_minutes() {
+        let line = log_line(
+            Duration::from_millis(6_061_250),
+            Stream::Err,
+            "still failing",
+        );
+        assert_eq!(line, "101:01.250 err still failing\n");
+
+        let line = OutputLine::parse(line.trim_end());
+        assert_eq!(line.elapsed(), "101:01.250");
+        assert_eq!(line.stream(), Stream::Err);
+        assert_eq!(line.text(), "still failing");
+    }
+}
@@ -0,0 +1,996 @@
+use crate::ui;
+use crate::ui::confirm::{ConfirmStep, Confirmation};
+use crate::ui::env_editor::{EnvEditor, EnvMessage};
+use crate::ui::event::{Event, EventSource};
+use crate::ui::history::QueryHistory;
+use crate::ui::input::ScriptInput;
+use crate::ui::keymap::{self, KeyMap};
+use crate::ui::log_browser::LogBrowser;
+use crate::ui::table::ScriptTable;
+use crate::ui::terminal::Tui;
+use crate::ui::theme::Theme;
+use crate::ui::vim::{Mode, Vim, VimCommand};
+use crossterm::event::{
+    Event as TerminalEvent, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use ratatui::{
+    layout::{Position, Rect},
+    text::Line,
+    widgets::{Block, Clear, Scrollbar, ScrollbarOrientation},
+    Frame,
+};
+use scriptor::{Config, ProjectStore, RunEnv, RunLogs, RunRequest, Scripts};
+use std::{
+    cell::Cell,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+const TICK_RATE: Duration = Duration::from_millis(250);
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+pub enum Selection {
+    /// A script to run, with the environment picked for this run if one was.
+    Script {
+        name: String,
+        env: Option<RunEnv>,
+    },
+    Rerun(RunRequest),
+}
+
+pub enum State {
+    Picking,
+    /// Editing the environment to run a script with.
+    EditingEnv(EnvEditor),
+    /// Looking through the output of past runs.
+    BrowsingLogs(LogBrowser),
+    /// Waiting for a risky script to be confirmed before finishing with it.
+    Confirming(Confirmation),
+    /// Finished, with the script to run unless the user quit.
+    Done(Option<Selection>),
+}
+
+/// A change to the app, decoded from an event by `App::action` and applied by `App::update`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    /// An action bound in the key map.
+    Bound(keymap::Action),
+    Vim(VimCommand),
+    Insert(char),
+    Paste(String),
+    Click(Position),
+    HistorySearch(SearchStep),
+    Confirm(ConfirmStep),
+    ReloadScripts,
+}
+
+/// A key pressed while searching the query history.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchStep {
+    Older,
+    Push(char),
+    Pop,
+    Accept,
+    Cancel,
+}
+
+pub struct App {
+    state: State,
+    script_table: ScriptTable,
+    script_input: ScriptInput,
+    history: QueryHistory,
+    store: ProjectStore,
+    run_logs: RunLogs,
+    // The project whose package.json is watched and reloaded, unless the scripts were given
+    project_dir: Option<PathBuf>,
+    config: Config,
+    key_map: KeyMap,
+    show_preview: bool,
+    vim: Option<Vim>,
+    theme: Theme,
+    // Where the last frame drew the table and search box, for finding what was clicked
+    table_area: Cell<Rect>,
+    input_area: Cell<Rect>,
+    table_offset: Cell<usize>,
+    last_click: Option<(Instant, usize)>,
+}
+
+impl App {
+    /// Picks from the scripts in `project_dir`, reloading them when its package.json changes.
+    pub fn new(project_dir: &Path, config: Config, key_map: KeyMap, theme: Theme) -> Self {
+        let scripts = Scripts::load(project_dir);
+        let store = ProjectStore::for_project(project_dir);
+        let run_logs = RunLogs::for_project(project_dir);
+
+        Self {
+            project_dir: Some(project_dir.to_path_buf()),
+            ..Self::from_scripts(scripts, store, run_logs, config, key_map, theme)
+        }
+    }
+
+    /// Picks from `scripts`, keeping pins and history in `store` and browsing runs in `run_logs`.
+    pub fn from_scripts(
+        mut scripts: Scripts,
+        store: ProjectStore,
+        run_logs: RunLogs,
+        config: Config,
+        key_map: KeyMap,
+        theme: Theme,
+    ) -> Self {
+        scripts.hide(&config);
+
+        let script_table = ScriptTable::from_scripts(scripts)
+            .with_pinned(store.pinned())
+            .with_sort(config.sort(), store.recent_scripts());
+        let history = QueryHistory::new(store.queries());
+        let script_input = ScriptInput::new();
+        let vim = config.vim_mode().then(Vim::new);
+
+        Self {
+            state: State::Picking,
+            script_table,
+            script_input,
+            history,
+            store,
+            run_logs,
+            project_dir: None,
+            config,
+            key_map,
+            show_preview: true,
+            vim,
+            theme,
+            table_area: Cell::default(),
+            input_area: Cell::default(),
+            table_offset: Cell::new(0),
+            last_click: None,
+        }
+    }
+
+    fn save_pins(&self) -> std::io::Result<()> {
+        self.store.save_pinned(self.script_table.pinned_scripts())
+    }
+
+    pub fn run(mut self, mut terminal: Tui) -> color_eyre::Result<Option<Selection>> {
+        let mut events = EventSource::new(TICK_RATE);
+
+        if let Some(project_dir) = &self.project_dir {
+            // The picker still works without live reload when the files can't be watched
+            let _ = events.watch_scripts(&Scripts::source_files(project_dir));
+        }
+
+        loop {
+            if let State::Done(selection) = self.state {
+                return Ok(selection);
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+            *self.script_table.state.offset_mut() = self.table_offset.get();
+
+            if let Some(action) = self.action(events.next()?) {
+                self.update(action)?;
+            }
+        }
+    }
+
+    /// Decodes an event into the action it stands for, if any.
+    pub fn action(&mut self, event: Event) -> Option<Action> {
+        let in_dialog = matches!(self.state, State::Confirming(_) | State::EditingEnv(_));
+
+        let key = match event {
+            Event::Terminal(TerminalEvent::Mouse(_)) if in_dialog => return None,
+            Event::Terminal(TerminalEvent::Key(key)) => key,
+            Event::Terminal(TerminalEvent::Mouse(mouse)) => return Self::mouse_action(mouse),
+            Event::Terminal(TerminalEvent::Paste(text)) => {
+                let is_typing = match self.state {
+                    State::Picking => self.vim.as_ref().is_none_or(|vim| vim.mode == Mode::Insert),
+                    State::EditingEnv(_) | State::BrowsingLogs(_) => true,
+                    State::Confirming(_) | State::Done(_) => false,
+                };
+
+                return is_typing.then_some(Action::Paste(text));
+            }
+            Event::ScriptsChanged => return Some(Action::ReloadScripts),
+            // The next frame is drawn after every event, which covers resizes and ticks
+            Event::Terminal(_) | Event::Tick => return None,
+        };
+
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+
+        let is_typed = !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+
+        if let State::Confirming(_) = self.state {
+            let step = match (self.key_map.action(key), key.code) {
+                (Some(keymap::Action::Run), _) => ConfirmStep::Accept,
+                (Some(keymap::Action::Cancel), _) => ConfirmStep::Reject,
+                (Some(keymap::Action::DeleteChar), _) => ConfirmStep::Pop,
+                (_, KeyCode::Char(c)) if is_typed => ConfirmStep::Push(c),
+                _ => return None,
+            };
+
+            return Some(Action::Confirm(step));
+        }
+
+        if let State::EditingEnv(_) | State::BrowsingLogs(_) = self.state {
+            return match (self.key_map.action(key), key.code) {
+                (Some(action), _) => Some(Action::Bound(action)),
+                (None, KeyCode::Char(c)) if is_typed => Some(Action::Insert(c)),
+                _ => None,
+            };
+        }
+
+        if self.history.search_pattern().is_some() {
+            let step = match (self.key_map.action(key), key.code) {
+                (Some(keymap::Action::HistorySearch), _) => Some(SearchStep::Older),
+                (Some(keymap::Action::DeleteChar), _) => Some(SearchStep::Pop),
+                (Some(keymap::Action::Run), _) => Some(SearchStep::Accept),
+                (Some(keymap::Action::Cancel), _) => Some(SearchStep::Cancel),
+                (None, KeyCode::Char(c)) if is_typed => Some(SearchStep::Push(c)),
+                // Other keys end the search and are then handled as usual
+                _ => None,
+            };
+
+            if let Some(step) = step {
+                return Some(Action::HistorySearch(step));
+            }
+        }
+
+        if let Some(command) = self.vim.as_mut().and_then(|vim| vim.handle(key)) {
+            return Some(Action::Vim(command));
+        }
+
+        match (self.key_map.action(key), key.code) {
+            (Some(action), _) => Some(Action::Bound(action)),
+            (None, KeyCode::Char(c)) if is_typed => Some(Action::Insert(c)),
+            _ => None,
+        }
+    }
+
+    /// Clicks select rows and the wheel moves the selection.
+    fn mouse_action(mouse: MouseEvent) -> Option<Action> {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => Some(Action::Bound(keymap::Action::Next)),
+            MouseEventKind::ScrollUp => Some(Action::Bound(keymap::Action::Previous)),
+            MouseEventKind::Down(MouseButton::Left) => {
+                Some(Action::Click(Position::new(mouse.column, mouse.row)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Applies an action to the app's state.
+    pub fn update(&mut self, action: Action) -> color_eyre::Result<()> {
+        match self.state {
+            State::EditingEnv(_) => return self.update_env(action),
+            State::BrowsingLogs(_) => return self.update_logs(action),
+            _ => {}
+        }
+
+        if self.history.search_pattern().is_some()
+            && !matches!(action, Action::HistorySearch(_) | Action::ReloadScripts)
+        {
+            self.history.finish_search(false);
+        }
+
+        let query = self.script_input.input.clone();
+
+        match &action {
+            Action::Bound(bound) => self.update_bound(*bound)?,
+            Action::Vim(command) => {
+                let half_page = (self.visible_rows() / 2).max(1) as isize;
+
+                match command {
+                    VimCommand::Next => self.script_table.next(),
+                    VimCommand::Previous => self.script_table.previous(),
+                    VimCommand::First => self.script_table.select_first(),
+                    VimCommand::Last => self.script_table.select_last(),
+                    VimCommand::HalfPageDown => self.script_table.move_selection(half_page),
+                    VimCommand::HalfPageUp => self.script_table.move_selection(-half_page),
+                    VimCommand::Quit => self.state = State::Done(None),
+                    VimCommand::Insert | VimCommand::Normal | VimCommand::Ignore => {}
+                }
+            }
+            Action::Insert(c) => self.script_input.enter_char(*c),
+            Action::Paste(text) => self.script_input.insert_str(text),
+            Action::Click(position) => self.click(*position)?,
+            Action::HistorySearch(step) => {
+                let found = match step {
+                    SearchStep::Older => self.history.search_older(),
+                    SearchStep::Push(c) => self.history.push_search_char(*c),
+                    SearchStep::Pop => self.history.pop_search_char(),
+                    SearchStep::Accept => self.history.finish_search(false),
+                    SearchStep::Cancel => self.history.finish_search(true),
+                };
+
+                if let Some(query) = found {
+                    self.script_input.set_input(query);
+                }
+            }
+            Action::Confirm(step) => self.confirm(step),
+            Action::ReloadScripts => self.reload_scripts(),
+        }
+
+        if self.script_input.input != query {
+            let browsing_history = matches!(
+                action,
+                Action::HistorySearch(_)
+                    | Action::Bound(keymap::Action::HistoryPrevious | keymap::Action::HistoryNext)
+            );
+
+            if !browsing_history {
+                self.history.reset();
+            }
+            self.refilter();
+        }
+
+        Ok(())
+    }
+
+    fn reload_scripts(&mut self) {
+        if let Some(project_dir) = &self.project_dir {
+            let mut scripts = Scripts::load(project_dir);
+            scripts.hide(&self.config);
+
+            self.script_table.reload(scripts);
+        }
+    }
+
+    /// Applies an action while the environment editor is open.
+    fn update_env(&mut self, action: Action) -> color_eyre::Result<()> {
+        let State::EditingEnv(editor) = &mut self.state else {
+            return Ok(());
+        };
+
+        match action {
+            Action::Bound(keymap::Action::Cancel) => self.state = State::Picking,
+            Action::Bound(keymap::Action::Run) => match editor.env() {
+                Ok(env) => {
+                    let name = editor.script.clone();
+                    self.pick(Selection::Script {
+                        name,
+                        env: Some(env),
+                    });
+                }
+                Err(error) => editor.message = Some(EnvMessage::Error(error.to_string())),
+            },
+            Action::Bound(keymap::Action::SaveEnv) => match editor.env() {
+                Ok(env) => {
+                    self.store.save_env_preset(&editor.script, env)?;
+                    editor.message = Some(EnvMessage::Saved);
+                }
+                Err(error) => editor.message = Some(EnvMessage::Error(error.to_string())),
+            },
+            Action::Bound(bound) => editor.edit(bound),
+            Action::Insert(c) => editor.insert_str(&c.to_string()),
+            Action::Paste(text) => editor.insert_str(&text),
+            Action::ReloadScripts => self.reload_scripts(),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Applies an action while browsing run logs.
+    fn update_logs(&mut self, action: Action) -> color_eyre::Result<()> {
+        let State::BrowsingLogs(browser) = &mut self.state else {
+            return Ok(());
+        };
+
+        match action {
+            Action::Bound(keymap::Action::Cancel) => self.state = State::Picking,
+            Action::Bound(bound) => browser.update(bound),
+            Action::Insert(c) => browser.insert_str(&c.to_string()),
+            Action::Paste(text) => browser.insert_str(&text),
+            Action::ReloadScripts => self.reload_scripts(),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn update_bound(&mut self, action: keymap::Action) -> color_eyre::Result<()> {
+        use keymap::Action;
+
+        match action {
+            Action::Run => self.activate()?,
+            Action::RerunLast => {
+                if let Some(last_run) = self.store.last_run() {
+                    self.pick(Selection::Rerun(last_run));
+                }
+            }
+            Action::TogglePin => {
+                self.script_table.toggle_pin();
+                self.save_pins()?;
+            }
+            Action::ToggleTree => self.script_table.toggle_view(),
+            Action::Expand => self.script_table.expand(),
+            Action::Collapse => self.script_table.collapse(),
+            Action::TogglePreview => self.show_preview = !self.show_preview,
+            Action::EditEnv => {
+                if let Some(name) = self.script_table.get_script_name() {
+                    let preset = self.store.env_preset(&name);
+
+                    self.state = State::EditingEnv(EnvEditor::new(name, preset));
+                }
+            }
+            // Only does anything in the environment editor
+            Action::SaveEnv => {}
+            Action::ShowLogs => {
+                self.state = State::BrowsingLogs(LogBrowser::new(self.run_logs.clone()));
+            }
+            Action::DeleteChar
+            | Action::DeleteCharForward
+            | Action::DeleteWord
+            | Action::ClearLine
+            | Action::CursorLeft
+            | Action::CursorRight
+            | Action::CursorStart
+            | Action::CursorEnd
+            | Action::CursorWordLeft
+            | Action::CursorWordRight => {
+                self.script_input.edit(action);
+            }
+            Action::HistoryPrevious => {
+                if let Some(query) = self.history.previous(&self.script_input.input) {
+                    self.script_input.set_input(query);
+                }
+            }
+            Action::HistoryNext => {
+                if let Some(query) = self.history.next() {
+                    self.script_input.set_input(query);
+                }
+            }
+            Action::HistorySearch => self.history.start_search(&self.script_input.input),
+            Action::Cancel => self.state = State::Done(None),
+            Action::Previous => self.script_table.previous(),
+            Action::Next => self.script_table.next(),
+            Action::PageUp => self.script_table.move_selection(-self.page_size()),
+            Action::PageDown => self.script_table.move_selection(self.page_size()),
+            Action::First => self.script_table.select_first(),
+            Action::Last => self.script_table.select_last(),
+        }
+
+        Ok(())
+    }
+
+    /// Picks the selected script, or opens or closes the selected group in the tree view.
+    fn activate(&mut self) -> color_eyre::Result<()> {
+        if self.script_table.toggle_group() {
+            return Ok(());
+        }
+
+        let Some(script_name) = self.script_table.get_script_name() else {
+            return Ok(());
+        };
+
+        if !self.script_input.input.is_empty() {
+            self.store.record_query(&self.script_input.input)?;
+        }
+
+        self.pick(Selection::Script {
+            name: script_name,
+            env: None,
+        });
+
+        Ok(())
+    }
+
+    /// Finishes with `selection`, first asking for confirmation if the script is risky.
+    fn pick(&mut self, selection: Selection) {
+        let name = match &selection {
+            Selection::Script { name, .. } => name.clone(),
+            Selection::Rerun(request) => request.script().to_string(),
+        };
+        let command = self
+            .script_table
+            .scripts
+            .command(&name)
+            .unwrap_or_default()
+            .to_string();
+
+        self.state = if self.config.needs_confirmation(&name, &command) {
+            let with = self.config.confirm_with();
+
+            State::Confirming(Confirmation::new(selection, name, command, with))
+        } else {
+            State::Done(Some(selection))
+        };
+    }
+
+    fn confirm(&mut self, step: &ConfirmStep) {
+        let State::Confirming(confirmation) = &mut self.state else {
+            return;
+        };
+
+        match confirmation.answer(step) {
+            None => {}
+            Some(false) => self.state = State::Picking,
+            Some(true) => {
+                if let State::Confirming(confirmation) =
+                    std::mem::replace(&mut self.state, State::Picking)
+                {
+                    self.state = State::Done(Some(confirmation.selection));
+                }
+            }
+        }
+    }
+
+    /// Clicking a row selects it and double clicking runs it, and clicking the search box moves
+    /// its cursor.
+    fn click(&mut self, position: Position) -> color_eyre::Result<()> {
+        let input_area = self.input_area.get();
+
+        if input_area.contains(position) {
+            self.script_input.click(input_area, position.x);
+            if let Some(vim) = self.vim.as_mut() {
+                vim.mode = Mode::Insert;
+            }
+            return Ok(());
+        }
+
+        let table_area = self.table_area.get();
+        let Some(index) = self.script_table.row_at(table_area, position.y) else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        let is_double_click = self
+            .last_click
+            .is_some_and(|(time, row)| row == index && now.duration_since(time) <= DOUBLE_CLICK);
+
+        self.script_table.select_index(index);
+
+        if is_double_click {
+            self.last_click = None;
+            return self.activate();
+        }
+
+        self.last_click = Some((now, index));
+
+        Ok(())
+    }
+
+    /// Lines of the table taken up by rows, less its borders and header.
+    fn table_lines(&self) -> u16 {
+        self.table_area.get().height.saturating_sub(3)
+    }
+
+    /// Rows that fit in the table, which depends on whether its columns are stacked.
+    fn visible_rows(&self) -> u16 {
+        let columns = self.script_table.columns(self.table_area.get().width);
+
+        self.table_lines() / columns.row_height()
+    }
+
+    fn page_size(&self) -> isize {
+        self.visible_rows().max(1) as isize
+    }
+
+    fn refilter(&mut self) {
+        self.script_table
+            .filter_results(self.script_input.input.clone());
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        if let State::BrowsingLogs(browser) = &self.state {
+            browser.draw(frame, &self.key_map, &self.theme);
+            return;
+        }
+
+        let [help_area, input_area, mut messages_area, preview_area] =
+            ui::layout::generate_layout(frame.area());
+
+        if !self.show_preview {
+            messages_area = messages_area.union(preview_area);
+        }
+
+        let mut input_field = self.script_input.generate_input(input_area, &self.theme);
+
+        if let Some(pattern) = self.history.search_pattern() {
+            let failed = if self.history.search_failed() {
+                "failing "
+            } else {
+                ""
+            };
+
+            input_field = input_field.block(
+                Block::bordered().title(format!("Search ({failed}history search: {pattern})")),
+            );
+        }
+
+        // The vim mode is shown at the end of the help line, with a space before it
+        let help_width = match &self.vim {
+            Some(vim) => help_area
+                .width
+                .saturating_sub(vim.status().len() as u16 + 1),
+            None => help_area.width,
+        };
+        frame.render_widget(
+            ui::help::generate_help_text(&self.key_map, &self.theme, help_width),
+            help_area,
+        );
+
+        frame.render_widget(input_field, input_area);
+
+        match &self.vim {
+            Some(vim) => {
+                frame.render_widget(Line::from(vim.status()).right_aligned(), help_area);
+
+                if vim.mode == Mode::Insert {
+                    frame.set_cursor_position(self.script_input.place_cursor(input_area));
+                }
+            }
+            None => frame.set_cursor_position(self.script_input.place_cursor(input_area)),
+        }
+
+        self.input_area.set(input_area);
+        self.table_area.set(messages_area);
+
+        let mut table_state = self.script_table.state.clone();
+        frame.render_stateful_widget(
+            self.script_table.generate_table(messages_area, &self.theme),
+            messages_area,
+            &mut table_state,
+        );
+        self.table_offset.set(table_state.offset());
+
+        if let Some(mut scrollbar_state) = self
+            .script_table
+            .scrollbar_state(self.visible_rows() as usize)
+        {
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None),
+                // Alongside the rows, below the top border and header
+                Rect {
+                    y: messages_area.y + 2,
+                    height: self.table_lines(),
+                    ..messages_area
+                },
+                &mut scrollbar_state,
+            );
+        }
+
+        if self.show_preview {
+            frame.render_widget(
+                ui::preview::generate_preview(&self.script_table, &self.theme),
+                preview_area,
+            );
+        }
+
+        if let State::EditingEnv(editor) = &self.state {
+            editor.draw(frame, &self.key_map, &self.theme);
+        }
+
+        if let State::Confirming(confirmation) = &self.state {
+            let area = confirmation.area(frame.area());
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                confirmation.generate_dialog(area, &self.key_map, &self.theme),
+                area,
+            );
+
+            if let Some(cursor) = confirmation.cursor(area) {
+                frame.set_cursor_position(cursor);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use crate::ui::table::sample_scripts;
+    use crossterm::event::KeyEvent;
+    use insta::assert_snapshot;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use std::{
+        collections::HashMap,
+        ops::{Deref, DerefMut},
+    };
+
+    /// An app whose store and run logs live in a directory of its own, removed with the app.
+    struct TestApp {
+        app: App,
+        _dir: TestDir,
+    }
+
+    impl Deref for TestApp {
+        type Target = App;
+
+        fn deref(&self) -> &App {
+            &self.app
+        }
+    }
+
+    impl DerefMut for TestApp {
+        fn deref_mut(&mut self) -> &mut App {
+            &mut self.app
+        }
+    }
+
+    fn app(config: Config) -> TestApp {
+        app_with(sample_scripts(), config)
+    }
+
+    fn app_with(scripts: Scripts, config: Config) -> TestApp {
+        let dir = TestDir::new();
+        let app = App::from_scripts(
+            scripts,
+            ProjectStore::new(dir.join("app.json")),
+            RunLogs::new(dir.join("logs")),
+            config,
+            KeyMap::default(),
+            Theme::default(),
+        );
+
+        TestApp { app, _dir: dir }
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Terminal(TerminalEvent::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+    }
+
+    fn send(app: &mut App, event: Event) {
+        if let Some(action) = app.action(event) {
+            app.update(action).unwrap();
+        }
+    }
+
+    #[test]
+    fn it_action_decodes_keys() {
+        let mut app = app(Config::default());
+
+        assert_eq!(
+            app.action(key(KeyCode::Enter)),
+            Some(Action::Bound(keymap::Action::Run))
+        );
+        assert_eq!(
+            app.action(key(KeyCode::Char('b'))),
+            Some(Action::Insert('b'))
+        );
+        assert_eq!(app.action(Event::Tick), None);
+        assert_eq!(
+            app.action(Event::ScriptsChanged),
+            Some(Action::ReloadScripts)
+        );
+    }
+
+    #[test]
+    fn it_update_typing_filters_scripts() {
+        let mut app = app(Config::default());
+
+        for c in "and".chars() {
+            send(&mut app, key(KeyCode::Char(c)));
+        }
+
+        assert_eq!(app.script_input.input, "and");
+        assert_eq!(app.script_table.filtered.len(), 1);
+        assert!(matches!(app.state, State::Picking));
+    }
+
+    #[test]
+    fn it_update_run_picks_selected_script() {
+        let mut app = app(Config::default());
+
+        send(&mut app, key(KeyCode::Down));
+        send(&mut app, key(KeyCode::Enter));
+
+        assert!(matches!(
+            app.state,
+            State::Done(Some(Selection::Script { ref name, .. })) if name == "ios"
+        ));
+    }
+
+    #[test]
+    fn it_update_cancel_finishes_without_script() {
+        let mut app = app(Config::default());
+
+        send(&mut app, key(KeyCode::Esc));
+
+        assert!(matches!(app.state, State::Done(None)));
+    }
+
+    fn risky_app(config: &str) -> TestApp {
+        let scripts = Scripts::from(HashMap::from([(
+            "deploy:prod".to_string(),
+            "vercel --prod".to_string(),
+        )]));
+
+        app_with(scripts, Config::parse(config).unwrap())
+    }
+
+    #[test]
+    fn it_update_confirms_risky_scripts() {
+        let mut app = risky_app("");
+
+        send(&mut app, key(KeyCode::Enter));
+        assert!(matches!(app.state, State::Confirming(_)));
+
+        send(&mut app, key(KeyCode::Char('n')));
+        assert!(matches!(app.state, State::Picking));
+        assert_eq!(app.script_input.input, "");
+
+        send(&mut app, key(KeyCode::Enter));
+        send(&mut app, key(KeyCode::Char('y')));
+        assert!(matches!(
+            app.state,
+            State::Done(Some(Selection::Script { ref name, .. })) if name == "deploy:prod"
+        ));
+    }
+
+    #[test]
+    fn it_update_confirms_by_typing_name() {
+        let mut app = risky_app(r#"confirm_with = "name""#);
+
+        send(&mut app, key(KeyCode::Enter));
+        for c in "deploy".chars() {
+            send(&mut app, key(KeyCode::Char(c)));
+        }
+        send(&mut app, key(KeyCode::Enter));
+        assert!(matches!(app.state, State::Confirming(_)));
+
+        for c in ":prod".chars() {
+            send(&mut app, key(KeyCode::Char(c)));
+        }
+        send(&mut app, key(KeyCode::Enter));
+        assert!(matches!(app.state, State::Done(Some(_))));
+    }
+
+    #[test]
+    fn it_update_runs_scripts_without_confirmation_when_disabled() {
+        let mut app = risky_app("confirm_scripts = []");
+
+        send(&mut app, key(KeyCode::Enter));
+
+        assert!(matches!(app.state, State::Done(Some(_))));
+    }
+
+    #[test]
+    fn it_update_runs_with_edited_env_and_saves_preset() {
+        let mut app = app(Config::default());
+        let save = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+
+        send(&mut app, key(KeyCode::F(3)));
+        for c in "DEBUG=1".chars() {
+            send(&mut app, key(KeyCode::Char(c)));
+        }
+        send(&mut app, Event::Terminal(TerminalEvent::Key(save)));
+        assert_eq!(
+            app.store.env_preset("android"),
+            Some(RunEnv::parse("DEBUG=1").unwrap())
+        );
+
+        send(&mut app, key(KeyCode::Enter));
+        assert!(matches!(
+            app.state,
+            State::Done(Some(Selection::Script { env: Some(ref env), .. })) if env.vars()["DEBUG"] == "1"
+        ));
+    }
+
+    #[test]
+    fn it_update_keeps_editor_open_on_invalid_env() {
+        let mut app = app(Config::default());
+
+        send(&mut app, key(KeyCode::F(3)));
+        send(
+            &mut app,
+            Event::Terminal(TerminalEvent::Paste("1X=2".to_string())),
+        );
+        send(&mut app, key(KeyCode::Enter));
+        assert!(matches!(
+            app.state,
+            State::EditingEnv(EnvEditor {
+                message: Some(EnvMessage::Error(_)),
+                ..
+            })
+        ));
+
+        send(&mut app, key(KeyCode::Esc));
+        assert!(matches!(app.state, State::Picking));
+    }
+
+    #[test]
+    fn it_update_browses_logs_and_goes_back() {
+        let mut app = app(Config::default());
+
+        send(&mut app, key(KeyCode::F(4)));
+        assert!(matches!(app.state, State::BrowsingLogs(_)));
+
+        send(&mut app, key(KeyCode::Char('x')));
+        assert!(matches!(
+            app.state,
+            State::BrowsingLogs(ref browser) if browser.input.input == "x"
+        ));
+        assert_eq!(app.script_input.input, "");
+
+        send(&mut app, key(KeyCode::Esc));
+        assert!(matches!(app.state, State::Picking));
+    }
+
+    #[test]
+    fn it_draw_shows_env_editor() {
+        let mut app = risky_app("");
+        send(&mut app, key(KeyCode::F(3)));
+        send(
+            &mut app,
+            Event::Terminal(TerminalEvent::Paste(".env.test DEBUG=1".to_string())),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                app.draw(frame);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn it_draw_shows_confirmation() {
+        let mut app = risky_app(r#"confirm_with = "name""#);
+        send(&mut app, key(KeyCode::Enter));
+        send(&mut app, key(KeyCode::Char('d')));
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                app.draw(frame);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn it_draw_shows_vim_mode() {
+        let config = Config::parse("vim_mode = true").unwrap();
+        let app = app(config);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                app.draw(frame);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn it_draw_shows_scrollbar_when_rows_overflow() {
+        let app = app(Config::default());
+        let mut terminal = Terminal::new(TestBackend::new(80, 14)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                app.draw(frame);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn it_draw_snapshot() {
+        let app = app(Config::default());
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                app.draw(frame);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+}
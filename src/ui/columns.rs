@@ -0,0 +1,118 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Tables narrower than this show each script's command below its name instead of beside it.
+pub const NARROW_WIDTH: u16 = 60;
+
+const MIN_NAME_WIDTH: usize = 12;
+// Borders, the highlight bar, the pin marker and the spacing between columns
+const CHROME_WIDTH: usize = 2 + 3 + 1 + 2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Columns {
+    /// Name and command side by side, in columns this many characters wide.
+    Wide { name: usize, command: usize },
+    /// Name above command, both this wide.
+    Narrow { width: usize },
+}
+
+impl Columns {
+    /// Fits the name column to the longest name in `names`, up to half of a table `width`
+    /// characters wide, leaving the rest to the command.
+    pub fn fit<'a>(names: impl Iterator<Item = &'a str>, width: u16) -> Self {
+        let available = (width as usize).saturating_sub(CHROME_WIDTH);
+
+        if width < NARROW_WIDTH {
+            return Self::Narrow {
+                width: available + 1,
+            };
+        }
+
+        let longest = names.map(UnicodeWidthStr::width).max().unwrap_or(0);
+        let name = longest.clamp(MIN_NAME_WIDTH, (available / 2).max(MIN_NAME_WIDTH));
+
+        Self::Wide {
+            name,
+            command: available.saturating_sub(name),
+        }
+    }
+
+    /// Lines each row takes up.
+    pub fn row_height(&self) -> u16 {
+        match self {
+            Self::Wide { .. } => 1,
+            Self::Narrow { .. } => 2,
+        }
+    }
+}
+
+/// Cuts `text` down to `width` characters, ending it with `…` when anything was cut.
+pub fn ellipsize(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+
+    let mut used = 0;
+    let mut cut: String = text
+        .chars()
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used < width
+        })
+        .collect();
+    cut.push('…');
+
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_fit_sizes_name_column_to_content() {
+        let names = ["build", "build:web:production"];
+
+        assert_eq!(
+            Columns::fit(names.into_iter(), 80),
+            Columns::Wide {
+                name: 20,
+                command: 52
+            }
+        );
+        assert_eq!(
+            Columns::fit(["ios"].into_iter(), 80),
+            Columns::Wide {
+                name: 12,
+                command: 60
+            }
+        );
+    }
+
+    #[test]
+    fn it_fit_caps_name_column_at_half_width() {
+        let long_name = "x".repeat(100);
+
+        assert_eq!(
+            Columns::fit([long_name.as_str()].into_iter(), 80),
+            Columns::Wide {
+                name: 36,
+                command: 36
+            }
+        );
+    }
+
+    #[test]
+    fn it_fit_stacks_columns_when_narrow() {
+        assert_eq!(
+            Columns::fit(["build"].into_iter(), 40),
+            Columns::Narrow { width: 33 }
+        );
+    }
+
+    #[test]
+    fn it_ellipsize_cuts_long_text() {
+        assert_eq!(ellipsize("build", 5), "build");
+        assert_eq!(ellipsize("build:web", 6), "build…");
+        assert_eq!(ellipsize("日本語", 5), "日本…");
+    }
+}
@@ -0,0 +1,181 @@
+use crate::ui::app::Selection;
+use crate::ui::columns::ellipsize;
+use crate::ui::keymap::{Action, KeyMap};
+use crate::ui::layout::centered;
+use crate::ui::theme::Theme;
+use ratatui::{
+    layout::{Position, Rect},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use scriptor::ConfirmWith;
+use unicode_width::UnicodeWidthStr;
+
+const WIDTH: u16 = 60;
+
+/// A key pressed while confirming a script.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfirmStep {
+    Push(char),
+    Pop,
+    Accept,
+    Reject,
+}
+
+/// A picked script that has to be confirmed before it runs.
+pub struct Confirmation {
+    pub selection: Selection,
+    name: String,
+    command: String,
+    with: ConfirmWith,
+    typed: String,
+}
+
+impl Confirmation {
+    pub fn new(selection: Selection, name: String, command: String, with: ConfirmWith) -> Self {
+        Self {
+            selection,
+            name,
+            command,
+            with,
+            typed: String::new(),
+        }
+    }
+
+    /// Applies a key, returning whether the script was confirmed or rejected once it has been.
+    pub fn answer(&mut self, step: &ConfirmStep) -> Option<bool> {
+        match (self.with, step) {
+            (_, ConfirmStep::Reject) => Some(false),
+            (ConfirmWith::Yes, ConfirmStep::Push('y' | 'Y')) => Some(true),
+            (ConfirmWith::Yes, ConfirmStep::Push('n' | 'N')) => Some(false),
+            (ConfirmWith::Yes, _) => None,
+            (ConfirmWith::Name, ConfirmStep::Push(c)) => {
+                self.typed.push(*c);
+                None
+            }
+            (ConfirmWith::Name, ConfirmStep::Pop) => {
+                self.typed.pop();
+                None
+            }
+            (ConfirmWith::Name, ConfirmStep::Accept) => (self.typed == self.name).then_some(true),
+        }
+    }
+
+    /// Where the dialog goes, in the middle of `frame_area`.
+    pub fn area(&self, frame_area: Rect) -> Rect {
+        let height = match self.with {
+            ConfirmWith::Yes => 6,
+            ConfirmWith::Name => 7,
+        };
+
+        centered(frame_area, WIDTH, height)
+    }
+
+    /// The dialog to draw in `area`, with the script's command cut to fit.
+    pub fn generate_dialog(
+        &self,
+        area: Rect,
+        key_map: &KeyMap,
+        theme: &Theme,
+    ) -> Paragraph<'static> {
+        let key = |action: Action, fallback: &str| {
+            let binding = key_map
+                .binding(action)
+                .map_or_else(|| fallback.to_string(), |binding| binding.to_string());
+
+            Span::styled(binding, theme.key)
+        };
+
+        let mut lines = vec![
+            Line::from(vec![
+                "Run ".into(),
+                Span::styled(self.name.clone(), theme.warning),
+                "?".into(),
+            ]),
+            Line::styled(
+                ellipsize(&self.command, area.width.saturating_sub(2) as usize),
+                theme.muted,
+            ),
+            Line::default(),
+        ];
+
+        match self.with {
+            ConfirmWith::Yes => lines.push(Line::from(vec![
+                Span::styled("y", theme.key),
+                " run it  ".into(),
+                Span::styled("n", theme.key),
+                " or ".into(),
+                key(Action::Cancel, "esc"),
+                " go back".into(),
+            ])),
+            ConfirmWith::Name => {
+                lines.push(Line::from(vec![
+                    "Type the script name and press ".into(),
+                    key(Action::Run, "enter"),
+                    ", or ".into(),
+                    key(Action::Cancel, "esc"),
+                    " to go back".into(),
+                ]));
+                lines.push(Line::styled(format!("> {}", self.typed), theme.input));
+            }
+        }
+
+        Paragraph::new(lines).block(
+            Block::bordered()
+                .title("Confirm")
+                .border_style(theme.warning),
+        )
+    }
+
+    /// Where the cursor goes when the dialog is drawn in `area`, if the name has to be typed.
+    pub fn cursor(&self, area: Rect) -> Option<Position> {
+        (self.with == ConfirmWith::Name).then(|| {
+            Position::new(
+                area.x + 3 + self.typed.width() as u16,
+                area.y + area.height.saturating_sub(2),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirmation(with: ConfirmWith) -> Confirmation {
+        Confirmation::new(
+            Selection::Script {
+                name: "deploy".to_string(),
+                env: None,
+            },
+            "deploy".to_string(),
+            "vercel --prod".to_string(),
+            with,
+        )
+    }
+
+    #[test]
+    fn it_answer_confirms_with_y() {
+        let mut confirmation = confirmation(ConfirmWith::Yes);
+
+        assert_eq!(confirmation.answer(&ConfirmStep::Accept), None);
+        assert_eq!(confirmation.answer(&ConfirmStep::Push('y')), Some(true));
+        assert_eq!(confirmation.answer(&ConfirmStep::Push('n')), Some(false));
+    }
+
+    #[test]
+    fn it_answer_confirms_with_typed_name() {
+        let mut confirmation = confirmation(ConfirmWith::Name);
+
+        for c in "deplo".chars() {
+            confirmation.answer(&ConfirmStep::Push(c));
+        }
+        assert_eq!(confirmation.answer(&ConfirmStep::Accept), None);
+
+        confirmation.answer(&ConfirmStep::Push('x'));
+        confirmation.answer(&ConfirmStep::Pop);
+        confirmation.answer(&ConfirmStep::Push('y'));
+        assert_eq!(confirmation.answer(&ConfirmStep::Accept), Some(true));
+        assert_eq!(confirmation.answer(&ConfirmStep::Reject), Some(false));
+    }
+}
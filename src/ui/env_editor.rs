@@ -0,0 +1,133 @@
+use crate::ui::input::ScriptInput;
+use crate::ui::keymap::{Action, KeyMap};
+use crate::ui::layout::centered;
+use crate::ui::theme::Theme;
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout},
+    text::{Line, Span},
+    widgets::{Block, Clear},
+    Frame,
+};
+use scriptor::RunEnv;
+
+const WIDTH: u16 = 70;
+const HEIGHT: u16 = 7;
+
+pub enum EnvMessage {
+    Saved,
+    Error(String),
+}
+
+/// Edits the environment a script runs with, as `KEY=value` words and env files to load.
+pub struct EnvEditor {
+    pub script: String,
+    pub input: ScriptInput,
+    pub message: Option<EnvMessage>,
+}
+
+impl EnvEditor {
+    /// Starts from the script's saved preset, if it has one.
+    pub fn new(script: String, preset: Option<RunEnv>) -> Self {
+        let mut input = ScriptInput::new();
+        input.set_input(preset.map(|env| env.to_text()).unwrap_or_default());
+
+        Self {
+            script,
+            input,
+            message: None,
+        }
+    }
+
+    pub fn env(&self) -> Result<RunEnv> {
+        RunEnv::parse(&self.input.input)
+    }
+
+    /// Applies an editing action to the text, ignoring any others.
+    pub fn edit(&mut self, action: Action) {
+        if self.input.edit(action) {
+            self.message = None;
+        }
+    }
+
+    pub fn insert_str(&mut self, text: &str) {
+        self.input.insert_str(text);
+        self.message = None;
+    }
+
+    /// Draws the editor over the middle of the frame.
+    pub fn draw(&self, frame: &mut Frame, key_map: &KeyMap, theme: &Theme) {
+        let area = centered(frame.area(), WIDTH, HEIGHT);
+        let block = Block::bordered().title(format!("Environment: {}", self.script));
+        let [input_area, help_area, message_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(block.inner(area));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+        frame.render_widget(
+            self.input
+                .generate_input(input_area, theme)
+                .block(Block::bordered().title("KEY=value or env file, e.g. .env.test DEBUG=1")),
+            input_area,
+        );
+        frame.set_cursor_position(self.input.place_cursor(input_area));
+
+        let key = |action: Action| {
+            let binding = key_map
+                .binding(action)
+                .map_or_else(|| "unbound".to_string(), |binding| binding.to_string());
+
+            Span::styled(binding, theme.key)
+        };
+        frame.render_widget(
+            Line::from(vec![
+                key(Action::Run),
+                " run  ".into(),
+                key(Action::SaveEnv),
+                format!(" save for {}  ", self.script).into(),
+                key(Action::Cancel),
+                " back".into(),
+            ]),
+            help_area,
+        );
+
+        let message = match &self.message {
+            Some(EnvMessage::Saved) => Line::styled("Saved", theme.muted),
+            Some(EnvMessage::Error(error)) => Line::styled(error.clone(), theme.warning),
+            None => Line::default(),
+        };
+        frame.render_widget(message, message_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_new_starts_from_preset() {
+        let editor = EnvEditor::new(
+            "test".to_string(),
+            Some(RunEnv::parse(".env.test DEBUG=1").unwrap()),
+        );
+
+        assert_eq!(editor.input.input, ".env.test DEBUG=1");
+        assert_eq!(editor.env().unwrap().vars()["DEBUG"], "1");
+    }
+
+    #[test]
+    fn it_edit_clears_message() {
+        let mut editor = EnvEditor::new("test".to_string(), None);
+        editor.insert_str("DEBUG=1");
+        editor.message = Some(EnvMessage::Saved);
+
+        editor.edit(Action::DeleteChar);
+
+        assert_eq!(editor.input.input, "DEBUG=");
+        assert!(editor.message.is_none());
+    }
+}
@@ -0,0 +1,113 @@
+use crossterm::event;
+use notify::RecommendedWatcher;
+use scriptor::watch_sources;
+use std::{
+    io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+// How often the terminal reader checks whether it should stop
+const STOP_CHECK: Duration = Duration::from_millis(50);
+
+#[derive(Debug)]
+pub enum Event {
+    Terminal(event::Event),
+    Tick,
+    /// A file the scripts are read from changed.
+    ScriptsChanged,
+}
+
+/// Merges terminal events, ticks and file changes into one stream for the app to handle.
+///
+/// Terminal events are read on a background thread, which stops when the source is dropped so
+/// that a script run afterwards gets the terminal's input to itself.
+pub struct EventSource {
+    sender: Sender<io::Result<Event>>,
+    receiver: Receiver<io::Result<Event>>,
+    stop: Arc<AtomicBool>,
+    reader: Option<JoinHandle<()>>,
+    watchers: Vec<RecommendedWatcher>,
+}
+
+impl EventSource {
+    /// Starts reading terminal events, sending a tick every `tick_rate` in between.
+    pub fn new(tick_rate: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let reader = {
+            let sender = sender.clone();
+            let stop = stop.clone();
+
+            thread::spawn(move || read_terminal(sender, tick_rate, stop))
+        };
+
+        Self {
+            sender,
+            receiver,
+            stop,
+            reader: Some(reader),
+            watchers: vec![],
+        }
+    }
+
+    /// Also sends `Event::ScriptsChanged` whenever any of `files` change.
+    pub fn watch_scripts(&mut self, files: &[PathBuf]) -> notify::Result<()> {
+        let sender = self.sender.clone();
+        let watcher = watch_sources(files, move || {
+            let _ = sender.send(Ok(Event::ScriptsChanged));
+        })?;
+        self.watchers.push(watcher);
+
+        Ok(())
+    }
+
+    /// Waits for the next event, failing if the terminal could not be read.
+    pub fn next(&self) -> io::Result<Event> {
+        self.receiver.recv().map_err(io::Error::other)?
+    }
+}
+
+impl Drop for EventSource {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
+
+fn read_terminal(sender: Sender<io::Result<Event>>, tick_rate: Duration, stop: Arc<AtomicBool>) {
+    let mut last_tick = Instant::now();
+
+    while !stop.load(Ordering::Relaxed) {
+        let timeout = tick_rate
+            .saturating_sub(last_tick.elapsed())
+            .min(STOP_CHECK);
+
+        let event = match event::poll(timeout) {
+            Ok(true) => event::read().map(Event::Terminal),
+            Ok(false) if last_tick.elapsed() >= tick_rate => {
+                last_tick = Instant::now();
+                Ok(Event::Tick)
+            }
+            Ok(false) => continue,
+            Err(error) => Err(error),
+        };
+
+        let failed = event.is_err();
+
+        // Reading stops after an error, which the app sees as the next event
+        if sender.send(event).is_err() || failed {
+            return;
+        }
+    }
+}
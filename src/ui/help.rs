@@ -1,14 +1,32 @@
+use crate::ui::keymap::KeyMap;
+use crate::ui::theme::Theme;
 use ratatui::{
-    style::{Modifier, Style, Stylize},
-    text::{Line, Text},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
     widgets::Paragraph,
 };
+use unicode_width::UnicodeWidthStr;
 
-pub fn generate_help_text() -> Paragraph<'static> {
-    let (msg, style) = (
-        vec!["Press ".into(), "esc".bold(), " to exit, ".into()],
-        Style::default().add_modifier(Modifier::RAPID_BLINK),
-    );
+/// The help line, keeping only the entries that fit in `width` columns.
+pub fn generate_help_text(key_map: &KeyMap, theme: &Theme, width: u16) -> Paragraph<'static> {
+    let mut used = 0;
+    let msg: Vec<Span> = key_map
+        .help()
+        .into_iter()
+        .take_while(|(key, description)| {
+            // The separator after the last entry may be cut off
+            used += key.width() + description.width() + 3;
+            used <= usize::from(width) + 2
+        })
+        .flat_map(|(key, description)| {
+            [
+                Span::styled(key, theme.key),
+                format!(" {description}  ").into(),
+            ]
+        })
+        .collect();
+
+    let (msg, style) = (msg, Style::default().add_modifier(Modifier::RAPID_BLINK));
     let text = Text::from(Line::from(msg)).patch_style(style);
 
     Paragraph::new(text)
@@ -20,15 +38,16 @@ mod tests {
     use crate::ui::layout::generate_layout;
     use insta::assert_snapshot;
     use ratatui::{backend::TestBackend, Terminal};
+    use std::collections::HashMap;
 
     #[test]
     fn it_help_text_has_correct_data() {
-        let paragraph = generate_help_text();
+        let paragraph = generate_help_text(&KeyMap::default(), &Theme::default(), 80);
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| {
-                let [help_area, _, _] = generate_layout(frame.area());
+                let [help_area, _, _, _] = generate_layout(frame.area());
 
                 frame.render_widget(&paragraph, help_area)
             })
@@ -36,4 +55,30 @@ mod tests {
 
         assert_snapshot!(terminal.backend());
     }
+
+    #[test]
+    fn it_help_text_shows_configured_keys() {
+        let keys = HashMap::from([("cancel".to_string(), "ctrl-q".to_string())]);
+        let paragraph =
+            generate_help_text(&KeyMap::from_config(&keys).unwrap(), &Theme::default(), 80);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 1)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&paragraph, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn it_help_text_drops_entries_that_dont_fit() {
+        let paragraph = generate_help_text(&KeyMap::default(), &Theme::default(), 40);
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 1)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&paragraph, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
 }
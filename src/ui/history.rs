@@ -0,0 +1,205 @@
+/// Previous search queries, oldest first, which can be stepped through or searched like a
+/// shell's history.
+pub struct QueryHistory {
+    entries: Vec<String>,
+    position: Option<usize>,
+    draft: String,
+    search: Option<HistorySearch>,
+}
+
+/// A reverse incremental search through the history, like a shell's `ctrl-r`.
+struct HistorySearch {
+    pattern: String,
+    original: String,
+    matched: Option<usize>,
+}
+
+impl QueryHistory {
+    pub fn new(entries: Vec<String>) -> Self {
+        Self {
+            entries,
+            position: None,
+            draft: String::new(),
+            search: None,
+        }
+    }
+
+    /// Steps back to an older query, remembering `current` so that stepping forward past the
+    /// newest query brings it back.
+    pub fn previous(&mut self, current: &str) -> Option<String> {
+        let position = match self.position {
+            Some(position) => position.saturating_sub(1),
+            None => {
+                self.draft = current.to_string();
+                self.entries.len().checked_sub(1)?
+            }
+        };
+
+        self.position = Some(position);
+        Some(self.entries[position].clone())
+    }
+
+    pub fn next(&mut self) -> Option<String> {
+        let position = self.position? + 1;
+
+        if position < self.entries.len() {
+            self.position = Some(position);
+            Some(self.entries[position].clone())
+        } else {
+            self.position = None;
+            Some(std::mem::take(&mut self.draft))
+        }
+    }
+
+    /// Stops stepping through the history, as the query has been edited.
+    pub fn reset(&mut self) {
+        self.position = None;
+    }
+
+    pub fn start_search(&mut self, current: &str) {
+        self.reset();
+        self.search = Some(HistorySearch {
+            pattern: String::new(),
+            original: current.to_string(),
+            matched: None,
+        });
+    }
+
+    pub fn search_pattern(&self) -> Option<&str> {
+        self.search.as_ref().map(|search| search.pattern.as_str())
+    }
+
+    /// Whether the search pattern matches nothing in the history.
+    pub fn search_failed(&self) -> bool {
+        self.search
+            .as_ref()
+            .is_some_and(|search| !search.pattern.is_empty() && search.matched.is_none())
+    }
+
+    /// Extends the pattern, keeping the current match if it still matches.
+    pub fn push_search_char(&mut self, c: char) -> Option<String> {
+        let search = self.search.as_mut()?;
+        search.pattern.push(c);
+        let before = search
+            .matched
+            .map_or(self.entries.len(), |matched| matched + 1);
+
+        self.find(before)
+    }
+
+    pub fn pop_search_char(&mut self) -> Option<String> {
+        self.search.as_mut()?.pattern.pop();
+
+        self.find(self.entries.len())
+    }
+
+    /// Moves on to the next older query matching the pattern.
+    pub fn search_older(&mut self) -> Option<String> {
+        let search = self.search.as_ref()?;
+        let before = search.matched.unwrap_or(self.entries.len());
+
+        self.find(before)
+    }
+
+    /// Ends the search, returning the query to go back to when `cancelled`.
+    pub fn finish_search(&mut self, cancelled: bool) -> Option<String> {
+        let search = self.search.take()?;
+
+        cancelled.then_some(search.original)
+    }
+
+    /// Finds the newest query before index `before` containing the pattern, keeping the
+    /// previous match when there is none.
+    fn find(&mut self, before: usize) -> Option<String> {
+        let search = self.search.as_mut()?;
+
+        if search.pattern.is_empty() {
+            search.matched = None;
+            return Some(search.original.clone());
+        }
+
+        let found = self.entries[..before]
+            .iter()
+            .rposition(|entry| entry.contains(&search.pattern));
+
+        match found {
+            Some(index) => {
+                search.matched = Some(index);
+                Some(self.entries[index].clone())
+            }
+            None => {
+                if !search
+                    .matched
+                    .is_some_and(|matched| self.entries[matched].contains(&search.pattern))
+                {
+                    search.matched = None;
+                }
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> QueryHistory {
+        QueryHistory::new(vec![
+            "build".to_string(),
+            "test".to_string(),
+            "build:web".to_string(),
+        ])
+    }
+
+    #[test]
+    fn it_steps_back_and_forward_to_draft() {
+        let mut history = history();
+
+        assert_eq!(history.previous("te"), Some("build:web".to_string()));
+        assert_eq!(history.previous("build:web"), Some("test".to_string()));
+        assert_eq!(history.previous("test"), Some("build".to_string()));
+        assert_eq!(history.previous("build"), Some("build".to_string()));
+
+        assert_eq!(history.next(), Some("test".to_string()));
+        assert_eq!(history.next(), Some("build:web".to_string()));
+        assert_eq!(history.next(), Some("te".to_string()));
+        assert_eq!(history.next(), None);
+    }
+
+    #[test]
+    fn it_previous_is_none_without_history() {
+        let mut history = QueryHistory::new(vec![]);
+
+        assert_eq!(history.previous("x"), None);
+        assert_eq!(history.next(), None);
+    }
+
+    #[test]
+    fn it_searches_older_matches() {
+        let mut history = history();
+        history.start_search("draft");
+
+        assert_eq!(history.push_search_char('b'), Some("build:web".to_string()));
+        assert_eq!(history.push_search_char('u'), Some("build:web".to_string()));
+        assert_eq!(history.search_older(), Some("build".to_string()));
+        assert_eq!(history.search_older(), None);
+        assert!(!history.search_failed());
+
+        assert_eq!(history.finish_search(false), None);
+        assert_eq!(history.search_pattern(), None);
+    }
+
+    #[test]
+    fn it_search_reports_failure_and_restores_on_cancel() {
+        let mut history = history();
+        history.start_search("draft");
+
+        assert_eq!(history.push_search_char('z'), None);
+        assert!(history.search_failed());
+        assert_eq!(history.pop_search_char(), Some("draft".to_string()));
+        assert!(!history.search_failed());
+
+        assert_eq!(history.finish_search(true), Some("draft".to_string()));
+    }
+}
@@ -1,8 +1,10 @@
+use crate::ui::keymap::Action;
+use crate::ui::theme::Theme;
 use ratatui::{
     layout::{Position, Rect},
-    style::{Color, Style},
     widgets::{Block, Paragraph},
 };
+use unicode_width::UnicodeWidthChar;
 
 pub struct ScriptInput {
     pub input: String,
@@ -31,16 +33,110 @@ impl ScriptInput {
         self.character_index = self.clamp_cursor(cursor_moved_right);
     }
 
+    pub fn move_cursor_start(&mut self) {
+        self.character_index = 0;
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        self.character_index = self.input.chars().count();
+    }
+
+    /// Moves to the start of the current or previous word, where a word is a run of letters and
+    /// digits so that `build:web` is two words.
+    pub fn move_word_left(&mut self) {
+        self.character_index = self.previous_word_start();
+    }
+
+    /// Moves past the end of the current or next word.
+    pub fn move_word_right(&mut self) {
+        let chars: Vec<char> = self.input.chars().collect();
+        let mut index = self.character_index;
+
+        while index < chars.len() && !chars[index].is_alphanumeric() {
+            index += 1;
+        }
+        while index < chars.len() && chars[index].is_alphanumeric() {
+            index += 1;
+        }
+
+        self.character_index = index;
+    }
+
+    fn previous_word_start(&self) -> usize {
+        let chars: Vec<char> = self.input.chars().collect();
+        let mut index = self.character_index;
+
+        while index > 0 && !chars[index - 1].is_alphanumeric() {
+            index -= 1;
+        }
+        while index > 0 && chars[index - 1].is_alphanumeric() {
+            index -= 1;
+        }
+
+        index
+    }
+
+    /// The number of characters scrolled off the left of an input box `width` columns wide,
+    /// keeping the cursor inside its borders. Wide characters such as CJK take two columns.
+    fn scroll_offset(&self, width: u16) -> usize {
+        let visible = width.saturating_sub(3).max(1) as usize;
+        let mut shown = self.column_between(0, self.character_index);
+        let mut offset = 0;
+
+        for c in self.input.chars() {
+            if shown <= visible {
+                break;
+            }
+
+            shown -= c.width().unwrap_or(0);
+            offset += 1;
+        }
+
+        offset
+    }
+
+    /// The display columns taken up by the characters from index `start` to `end`.
+    fn column_between(&self, start: usize, end: usize) -> usize {
+        self.input
+            .chars()
+            .take(end)
+            .skip(start)
+            .map(|c| c.width().unwrap_or(0))
+            .sum()
+    }
+
     pub fn place_cursor(&self, input_area: Rect) -> Position {
+        let offset = self.scroll_offset(input_area.width);
+        let column = self.column_between(offset, self.character_index);
+
         Position::new(
-            // Draw the cursor at the current position in the input field.
-            // This position is can be controlled via the left and right arrow key
-            input_area.x + self.character_index as u16 + 1,
+            // Draw the cursor at the current position in the input field, less whatever has
+            // scrolled out of view
+            input_area.x + column as u16 + 1,
             // Move one line down, from the border to the input line
             input_area.y + 1,
         )
     }
 
+    /// Moves the cursor to where the search box in `input_area` was clicked.
+    pub fn click(&mut self, input_area: Rect, column: u16) {
+        let clicked = column.saturating_sub(input_area.x + 1) as usize;
+        let offset = self.scroll_offset(input_area.width);
+
+        // Clicking anywhere on a wide character puts the cursor before it
+        let mut index = offset;
+        let mut start = 0;
+        for c in self.input.chars().skip(offset) {
+            start += c.width().unwrap_or(0);
+            if start > clicked {
+                break;
+            }
+            index += 1;
+        }
+
+        self.character_index = self.clamp_cursor(index);
+    }
+
     pub fn enter_char(&mut self, new_char: char) {
         let index = self.byte_index();
         self.input.insert(index, new_char);
@@ -50,6 +146,13 @@ impl ScriptInput {
         // self.state.select(Some(0));
     }
 
+    /// Inserts pasted text at the cursor, dropping line breaks since the query is a single line.
+    pub fn insert_str(&mut self, text: &str) {
+        for c in text.chars().filter(|c| !c.is_control()) {
+            self.enter_char(c);
+        }
+    }
+
     /// Returns the byte index based on the character position.
     ///
     /// Since each character in a string can contain multiple bytes, it's necessary to calculate
@@ -87,9 +190,62 @@ impl ScriptInput {
         }
     }
 
-    pub fn generate_input(&self) -> Paragraph<'static> {
-        Paragraph::new(self.input.clone())
-            .style(Style::default().fg(Color::Yellow))
+    /// Deletes the character under the cursor.
+    pub fn delete_char_forward(&mut self) {
+        if self.character_index < self.input.chars().count() {
+            self.move_cursor_right();
+            self.delete_char();
+        }
+    }
+
+    /// Deletes from the start of the previous word up to the cursor.
+    pub fn delete_word(&mut self) {
+        let start = self.previous_word_start();
+
+        self.input = self
+            .input
+            .chars()
+            .take(start)
+            .chain(self.input.chars().skip(self.character_index))
+            .collect();
+        self.character_index = start;
+    }
+
+    /// Replaces the query, leaving the cursor at the end of it.
+    pub fn set_input(&mut self, input: String) {
+        self.input = input;
+        self.move_cursor_end();
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.character_index = 0;
+    }
+
+    /// Applies an editing action, returning false for any other action.
+    pub fn edit(&mut self, action: Action) -> bool {
+        match action {
+            Action::DeleteChar => self.delete_char(),
+            Action::DeleteCharForward => self.delete_char_forward(),
+            Action::DeleteWord => self.delete_word(),
+            Action::ClearLine => self.clear(),
+            Action::CursorLeft => self.move_cursor_left(),
+            Action::CursorRight => self.move_cursor_right(),
+            Action::CursorStart => self.move_cursor_start(),
+            Action::CursorEnd => self.move_cursor_end(),
+            Action::CursorWordLeft => self.move_word_left(),
+            Action::CursorWordRight => self.move_word_right(),
+            _ => return false,
+        }
+
+        true
+    }
+
+    pub fn generate_input(&self, input_area: Rect, theme: &Theme) -> Paragraph<'static> {
+        let offset = self.scroll_offset(input_area.width);
+
+        Paragraph::new(self.input.chars().skip(offset).collect::<String>())
+            .style(theme.input)
             .block(Block::bordered().title("Search"))
     }
 }
@@ -183,16 +339,141 @@ mod tests {
         assert_eq!(script_input.character_index, 0);
     }
 
+    fn input_at(input: &str, character_index: usize) -> ScriptInput {
+        ScriptInput {
+            input: input.to_string(),
+            character_index,
+        }
+    }
+
+    #[test]
+    fn it_moves_to_start_and_end() {
+        let mut script_input = input_at("build", 2);
+
+        script_input.move_cursor_end();
+        assert_eq!(script_input.character_index, 5);
+
+        script_input.move_cursor_start();
+        assert_eq!(script_input.character_index, 0);
+    }
+
+    #[test]
+    fn it_moves_by_word() {
+        let mut script_input = input_at("build:web app", 13);
+
+        script_input.move_word_left();
+        assert_eq!(script_input.character_index, 10);
+        script_input.move_word_left();
+        assert_eq!(script_input.character_index, 6);
+
+        script_input.move_word_right();
+        assert_eq!(script_input.character_index, 9);
+        script_input.move_word_right();
+        assert_eq!(script_input.character_index, 13);
+    }
+
+    #[test]
+    fn it_delete_char_forward_removes_char_under_cursor() {
+        let mut script_input = input_at("abc", 1);
+
+        script_input.delete_char_forward();
+        assert_eq!(script_input.input, "ac");
+        assert_eq!(script_input.character_index, 1);
+
+        script_input.move_cursor_end();
+        script_input.delete_char_forward();
+        assert_eq!(script_input.input, "ac");
+    }
+
+    #[test]
+    fn it_delete_word_removes_previous_word() {
+        let mut script_input = input_at("test:unit --ci", 9);
+
+        script_input.delete_word();
+
+        assert_eq!(script_input.input, "test: --ci");
+        assert_eq!(script_input.character_index, 5);
+    }
+
+    #[test]
+    fn it_set_input_moves_cursor_to_end() {
+        let mut script_input = input_at("build", 0);
+
+        script_input.set_input("test:unit".to_string());
+
+        assert_eq!(script_input.input, "test:unit");
+        assert_eq!(script_input.character_index, 9);
+    }
+
+    #[test]
+    fn it_clear_empties_input() {
+        let mut script_input = input_at("build", 3);
+
+        script_input.clear();
+
+        assert_eq!(script_input.input, "");
+        assert_eq!(script_input.character_index, 0);
+    }
+
+    #[test]
+    fn it_insert_str_drops_line_breaks() {
+        let mut script_input = input_at("ad", 1);
+
+        script_input.insert_str("b\r\nc");
+
+        assert_eq!(script_input.input, "abcd");
+        assert_eq!(script_input.character_index, 3);
+    }
+
+    #[test]
+    fn it_scrolls_to_keep_cursor_in_view() {
+        let script_input = input_at("0123456789", 10);
+        let area = Rect::new(0, 0, 8, 3);
+
+        assert_eq!(script_input.scroll_offset(area.width), 5);
+        assert_eq!(script_input.place_cursor(area), Position::new(6, 1));
+        assert_eq!(input_at("0123", 4).scroll_offset(area.width), 0);
+    }
+
+    #[test]
+    fn it_click_places_cursor_under_pointer() {
+        let mut script_input = input_at("build", 0);
+        let area = Rect::new(2, 0, 20, 3);
+
+        script_input.click(area, 6);
+        assert_eq!(script_input.character_index, 3);
+
+        script_input.click(area, 19);
+        assert_eq!(script_input.character_index, 5);
+    }
+
+    #[test]
+    fn it_counts_wide_characters_as_two_columns() {
+        let mut script_input = input_at("ビルド", 3);
+
+        assert_eq!(
+            script_input.place_cursor(Rect::new(0, 0, 20, 3)),
+            Position::new(7, 1)
+        );
+
+        let narrow = Rect::new(0, 0, 8, 3);
+        assert_eq!(script_input.scroll_offset(narrow.width), 1);
+        assert_eq!(script_input.place_cursor(narrow), Position::new(5, 1));
+
+        script_input.click(Rect::new(2, 0, 20, 3), 6);
+        assert_eq!(script_input.character_index, 1);
+    }
+
     #[test]
     fn it_generate_input_renders_correctly() {
         let script_input = ScriptInput::new();
-        let input = script_input.generate_input();
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
 
         terminal
             .draw(|frame| {
-                let [_, input_area, _] = generate_layout(frame.area());
+                let [_, input_area, _, _] = generate_layout(frame.area());
+                let input = script_input.generate_input(input_area, &Theme::default());
 
                 frame.set_cursor_position(script_input.place_cursor(input_area));
                 frame.render_widget(&input, input_area)
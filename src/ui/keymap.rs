@@ -0,0 +1,452 @@
+use color_eyre::{eyre::eyre, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Run,
+    Cancel,
+    Next,
+    Previous,
+    TogglePin,
+    RerunLast,
+    ToggleTree,
+    Expand,
+    Collapse,
+    TogglePreview,
+    CursorLeft,
+    CursorRight,
+    CursorStart,
+    CursorEnd,
+    CursorWordLeft,
+    CursorWordRight,
+    DeleteChar,
+    DeleteCharForward,
+    DeleteWord,
+    ClearLine,
+    HistoryPrevious,
+    HistoryNext,
+    HistorySearch,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    EditEnv,
+    SaveEnv,
+    ShowLogs,
+}
+
+/// Every action with the name used for it in the `[keys]` config table and its default keys.
+const ACTIONS: [(Action, &str, &[&str]); 30] = [
+    (Action::Run, "run", &["enter"]),
+    (Action::Cancel, "cancel", &["esc"]),
+    (Action::Next, "next", &["down"]),
+    (Action::Previous, "previous", &["up"]),
+    (Action::PageUp, "page-up", &["pageup"]),
+    (Action::PageDown, "page-down", &["pagedown"]),
+    (Action::First, "first", &["ctrl-home"]),
+    (Action::Last, "last", &["ctrl-end"]),
+    (Action::TogglePin, "toggle-pin", &["tab"]),
+    (Action::RerunLast, "rerun-last", &["ctrl-l"]),
+    (Action::ToggleTree, "toggle-tree", &["ctrl-t"]),
+    (Action::Expand, "expand", &["ctrl-right"]),
+    (Action::Collapse, "collapse", &["ctrl-left"]),
+    (Action::TogglePreview, "toggle-preview", &["f2"]),
+    (Action::EditEnv, "edit-env", &["f3"]),
+    (Action::SaveEnv, "save-env", &["ctrl-s"]),
+    (Action::ShowLogs, "show-logs", &["f4"]),
+    (Action::CursorLeft, "cursor-left", &["left"]),
+    (Action::CursorRight, "cursor-right", &["right"]),
+    (Action::CursorStart, "cursor-start", &["home", "ctrl-a"]),
+    (Action::CursorEnd, "cursor-end", &["end", "ctrl-e"]),
+    (Action::CursorWordLeft, "cursor-word-left", &["alt-b"]),
+    (Action::CursorWordRight, "cursor-word-right", &["alt-f"]),
+    (Action::DeleteChar, "delete-char", &["backspace"]),
+    (
+        Action::DeleteCharForward,
+        "delete-char-forward",
+        &["delete"],
+    ),
+    (Action::DeleteWord, "delete-word", &["ctrl-w"]),
+    (Action::ClearLine, "clear-line", &["ctrl-u"]),
+    (Action::HistoryPrevious, "history-previous", &["ctrl-p"]),
+    (Action::HistoryNext, "history-next", &["ctrl-n"]),
+    (Action::HistorySearch, "history-search", &["ctrl-r"]),
+];
+
+/// Actions listed in the help line, with what they do. The most useful come first, as entries
+/// that don't fit are dropped from the end.
+const HELP: [(Action, &str); 7] = [
+    (Action::Cancel, "exit"),
+    (Action::TogglePin, "pin"),
+    (Action::RerunLast, "rerun last"),
+    (Action::ToggleTree, "tree view"),
+    (Action::ShowLogs, "logs"),
+    (Action::EditEnv, "env"),
+    (Action::TogglePreview, "details"),
+];
+
+const NAMED_KEYS: [(&str, KeyCode); 16] = [
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("space", KeyCode::Char(' ')),
+];
+
+const MODIFIERS: [(&str, KeyModifiers); 3] = [
+    ("ctrl", KeyModifiers::CONTROL),
+    ("alt", KeyModifiers::ALT),
+    ("shift", KeyModifiers::SHIFT),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Builds the binding a key event matches. Shift is dropped from characters, as the case of
+    /// the character already reflects it.
+    pub fn from_event(event: KeyEvent) -> Self {
+        let mut modifiers =
+            event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+
+        if matches!(event.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = color_eyre::Report;
+
+    /// Parses keys written like `enter`, `ctrl-l`, `alt-shift-left` or `f5`.
+    fn from_str(key: &str) -> Result<Self> {
+        let invalid = || eyre!("Invalid key `{key}`");
+
+        let mut parts: Vec<&str> = key.split('-').collect();
+        // A trailing `-` is the minus key itself, e.g. `ctrl--`
+        if parts.len() > 1 && parts.ends_with(&["", ""]) {
+            parts.truncate(parts.len() - 2);
+            parts.push("-");
+        }
+
+        let (name, modifier_names) = parts.split_last().ok_or_else(invalid)?;
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier_name in modifier_names {
+            let (_, modifier) = MODIFIERS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(modifier_name))
+                .ok_or_else(invalid)?;
+            modifiers |= *modifier;
+        }
+
+        let lower_name = name.to_ascii_lowercase();
+        let mut chars = name.chars();
+
+        let code = if let Some((_, code)) = NAMED_KEYS.iter().find(|(key, _)| *key == lower_name) {
+            *code
+        } else if let (Some(c), None) = (chars.next(), chars.next()) {
+            KeyCode::Char(c)
+        } else if let Some(number) = lower_name.strip_prefix('f') {
+            let number: u8 = number.parse().map_err(|_| invalid())?;
+            if !(1..=12).contains(&number) {
+                return Err(invalid());
+            }
+            KeyCode::F(number)
+        } else {
+            return Err(invalid());
+        };
+
+        Ok(Self::from_event(KeyEvent::new(code, modifiers)))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, modifier) in MODIFIERS {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}-")?;
+            }
+        }
+
+        match self.code {
+            KeyCode::F(number) => write!(f, "f{number}"),
+            code => match NAMED_KEYS.iter().find(|(_, named)| *named == code) {
+                Some((name, _)) => write!(f, "{name}"),
+                None => match code {
+                    KeyCode::Char(c) => write!(f, "{c}"),
+                    code => write!(f, "{code:?}"),
+                },
+            },
+        }
+    }
+}
+
+/// Maps keys to the actions they trigger.
+pub struct KeyMap {
+    bindings: HashMap<KeyBinding, Action>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let bindings = ACTIONS
+            .iter()
+            .flat_map(|(action, _, keys)| {
+                keys.iter()
+                    .map(|key| (key.parse().expect("default keys are valid"), *action))
+            })
+            .collect();
+
+        Self { bindings }
+    }
+}
+
+fn action_name(action: Action) -> &'static str {
+    ACTIONS
+        .iter()
+        .find(|(bound, _, _)| *bound == action)
+        .map_or("unknown", |(_, name, _)| name)
+}
+
+impl KeyMap {
+    /// Builds the key map from the `[keys]` config table, which maps action names to keys.
+    /// Actions that are not configured keep their default key, and a key can only be bound to
+    /// one action.
+    pub fn from_config(keys: &HashMap<String, String>) -> Result<Self> {
+        let mut key_map = Self::default();
+
+        let mut overrides: Vec<(&String, &String)> = keys.iter().collect();
+        overrides.sort();
+
+        let mut bindings = vec![];
+        for (action_name, key) in overrides {
+            let (action, _, _) = ACTIONS
+                .iter()
+                .find(|(_, name, _)| name == action_name)
+                .ok_or_else(|| eyre!("Unknown action `{action_name}` in [keys]"))?;
+            let binding: KeyBinding = key
+                .parse()
+                .map_err(|error| eyre!("{error} for action `{action_name}` in [keys]"))?;
+
+            bindings.push((binding, *action));
+        }
+
+        // Free the default keys of every configured action first, so that keys can be swapped
+        for (_, action) in &bindings {
+            key_map.bindings.retain(|_, bound| bound != action);
+        }
+
+        for (binding, action) in bindings {
+            key_map
+                .bind(binding, action)
+                .map_err(|error| eyre!("{error} in [keys]"))?;
+        }
+
+        Ok(key_map)
+    }
+
+    /// Binds `binding` to `action`, replacing any keys previously bound to the action. Fails if
+    /// the key is bound to another action, which would otherwise lose it.
+    pub fn bind(&mut self, binding: KeyBinding, action: Action) -> Result<()> {
+        if let Some(&other) = self.bindings.get(&binding) {
+            if other != action {
+                return Err(eyre!(
+                    "Key `{binding}` for action `{}` is already bound to `{}`",
+                    action_name(action),
+                    action_name(other)
+                ));
+            }
+        }
+
+        self.bindings.retain(|_, bound| *bound != action);
+        self.bindings.insert(binding, action);
+
+        Ok(())
+    }
+
+    pub fn action(&self, event: KeyEvent) -> Option<Action> {
+        self.bindings.get(&KeyBinding::from_event(event)).copied()
+    }
+
+    /// A key bound to `action`, picking the same one each time when there are several.
+    pub fn binding(&self, action: Action) -> Option<KeyBinding> {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| **bound == action)
+            .map(|(binding, _)| *binding)
+            .min_by_key(|binding| binding.to_string())
+    }
+
+    /// The bound actions to list in the help line, as (key, description) pairs.
+    pub fn help(&self) -> Vec<(String, &'static str)> {
+        HELP.iter()
+            .filter_map(|(action, description)| {
+                Some((self.binding(*action)?.to_string(), *description))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_keys_with_modifiers() {
+        let binding: KeyBinding = "ctrl-alt-x".parse().unwrap();
+
+        assert_eq!(
+            binding,
+            KeyBinding::from_event(KeyEvent::new(
+                KeyCode::Char('x'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+        assert_eq!(binding.to_string(), "ctrl-alt-x");
+    }
+
+    #[test]
+    fn it_parses_named_and_function_keys() {
+        assert_eq!("Enter".parse::<KeyBinding>().unwrap().to_string(), "enter");
+        assert_eq!(
+            "shift-f5".parse::<KeyBinding>().unwrap().to_string(),
+            "shift-f5"
+        );
+        assert_eq!(
+            "ctrl--".parse::<KeyBinding>().unwrap().to_string(),
+            "ctrl--"
+        );
+        assert_eq!("space".parse::<KeyBinding>().unwrap().to_string(), "space");
+    }
+
+    #[test]
+    fn it_rejects_invalid_keys() {
+        for key in ["", "ctrl-", "hyper-x", "f13", "enterr"] {
+            assert!(
+                key.parse::<KeyBinding>().is_err(),
+                "{key} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn it_default_matches_builtin_keys() {
+        let key_map = KeyMap::default();
+
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            Some(Action::Run)
+        );
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL)),
+            Some(Action::RerunLast)
+        );
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE)),
+            None
+        );
+    }
+
+    #[test]
+    fn it_default_binds_every_default_key() {
+        let key_map = KeyMap::default();
+
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE)),
+            Some(Action::CursorStart)
+        );
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL)),
+            Some(Action::CursorStart)
+        );
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::ALT)),
+            Some(Action::CursorWordRight)
+        );
+    }
+
+    #[test]
+    fn it_from_config_overrides_defaults() {
+        let keys = HashMap::from([("rerun-last".to_string(), "F5".to_string())]);
+
+        let key_map = KeyMap::from_config(&keys).unwrap();
+
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE)),
+            Some(Action::RerunLast)
+        );
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL)),
+            None
+        );
+    }
+
+    #[test]
+    fn it_from_config_reports_unknown_actions_and_keys() {
+        let unknown_action = HashMap::from([("launch".to_string(), "enter".to_string())]);
+        let invalid_key = HashMap::from([("run".to_string(), "ctrl-".to_string())]);
+
+        assert_eq!(
+            KeyMap::from_config(&unknown_action)
+                .err()
+                .unwrap()
+                .to_string(),
+            "Unknown action `launch` in [keys]"
+        );
+        assert_eq!(
+            KeyMap::from_config(&invalid_key).err().unwrap().to_string(),
+            "Invalid key `ctrl-` for action `run` in [keys]"
+        );
+    }
+
+    #[test]
+    fn it_from_config_rejects_keys_bound_twice() {
+        let stolen = HashMap::from([("run".to_string(), "ctrl-l".to_string())]);
+        let swapped = HashMap::from([
+            ("run".to_string(), "ctrl-l".to_string()),
+            ("rerun-last".to_string(), "enter".to_string()),
+        ]);
+
+        assert_eq!(
+            KeyMap::from_config(&stolen).err().unwrap().to_string(),
+            "Key `ctrl-l` for action `run` is already bound to `rerun-last` in [keys]"
+        );
+
+        let key_map = KeyMap::from_config(&swapped).unwrap();
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            Some(Action::RerunLast)
+        );
+    }
+
+    #[test]
+    fn it_shifted_characters_match_without_shift() {
+        let mut key_map = KeyMap::default();
+        key_map.bind("G".parse().unwrap(), Action::Next).unwrap();
+
+        assert_eq!(
+            key_map.action(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT)),
+            Some(Action::Next)
+        );
+    }
+}
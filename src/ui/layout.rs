@@ -1,11 +1,25 @@
 use ratatui::layout::{Constraint, Layout, Rect};
 
-pub fn generate_layout(frame_area: Rect) -> [Rect; 3] {
+pub fn generate_layout(frame_area: Rect) -> [Rect; 4] {
     let vertical = Layout::vertical([
         Constraint::Length(1),
         Constraint::Length(3),
         Constraint::Min(1),
+        Constraint::Length(6),
     ]);
 
     vertical.areas(frame_area)
 }
+
+/// A `width` by `height` area in the middle of `area`, shrunk to fit if it's too small.
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
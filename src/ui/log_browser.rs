@@ -0,0 +1,393 @@
+use crate::ui::input::ScriptInput;
+use crate::ui::keymap::{Action, KeyMap};
+use crate::ui::theme::Theme;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use scriptor::{OutputLine, RunLog, RunLogs, Stream};
+
+const SCROLL_STEP: u16 = 10;
+
+/// A run's output, with the text of each line lowercased once for searching.
+struct Output {
+    lines: Vec<OutputLine>,
+    search_lines: Vec<String>,
+}
+
+impl Output {
+    fn new(lines: Vec<OutputLine>) -> Self {
+        let search_lines = lines
+            .iter()
+            .map(|line| line.text().to_lowercase())
+            .collect();
+
+        Self {
+            lines,
+            search_lines,
+        }
+    }
+
+    /// The lines whose text contains `query`, which must be lowercase.
+    fn matching_lines<'a>(&'a self, query: &'a str) -> impl Iterator<Item = &'a OutputLine> + 'a {
+        self.lines
+            .iter()
+            .zip(&self.search_lines)
+            .filter(move |(_, search)| search.contains(query))
+            .map(|(line, _)| line)
+    }
+}
+
+/// Browses a project's captured runs, searching their scripts, commands and output.
+pub struct LogBrowser {
+    logs: RunLogs,
+    runs: Vec<RunLog>,
+    // Each run's output, read the first time it is searched or shown
+    outputs: Vec<Option<Output>>,
+    pub input: ScriptInput,
+    // Indices into `runs` of the runs matching the search
+    filtered: Vec<usize>,
+    pub state: TableState,
+    scroll: u16,
+}
+
+impl LogBrowser {
+    pub fn new(logs: RunLogs) -> Self {
+        let runs = logs.list();
+
+        let mut browser = Self {
+            logs,
+            outputs: runs.iter().map(|_| None).collect(),
+            runs,
+            input: ScriptInput::new(),
+            filtered: vec![],
+            state: TableState::default(),
+            scroll: 0,
+        };
+        browser.refilter();
+
+        browser
+    }
+
+    pub fn selected(&self) -> Option<&RunLog> {
+        let index = self.filtered.get(self.state.selected()?)?;
+
+        Some(&self.runs[*index])
+    }
+
+    /// Applies a key map action, ignoring any the browser doesn't use.
+    pub fn update(&mut self, action: Action) {
+        let query = self.input.input.clone();
+
+        match action {
+            Action::Next => self.move_selection(1),
+            Action::Previous => self.move_selection(-1),
+            Action::First => self.move_selection(isize::MIN),
+            Action::Last => self.move_selection(isize::MAX),
+            Action::PageDown => self.scroll = self.scroll.saturating_add(SCROLL_STEP),
+            Action::PageUp => self.scroll = self.scroll.saturating_sub(SCROLL_STEP),
+            _ => {
+                self.input.edit(action);
+            }
+        }
+
+        if self.input.input != query {
+            self.refilter();
+        }
+    }
+
+    pub fn insert_str(&mut self, text: &str) {
+        self.input.insert_str(text);
+        self.refilter();
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let Some(selected) = self.state.selected() else {
+            return;
+        };
+        let last = self.filtered.len().saturating_sub(1) as isize;
+
+        self.select((selected as isize).saturating_add(delta).clamp(0, last) as usize);
+    }
+
+    fn select(&mut self, position: usize) {
+        self.state.select(Some(position));
+        self.scroll = 0;
+
+        if let Some(&index) = self.filtered.get(position) {
+            self.load_output(index);
+        }
+    }
+
+    fn load_output(&mut self, index: usize) -> &Output {
+        let (logs, run) = (&self.logs, &self.runs[index]);
+
+        self.outputs[index].get_or_insert_with(|| Output::new(logs.output(run)))
+    }
+
+    /// Keeps the runs whose script, command or output contains the search, ignoring case and
+    /// the time and stream at the start of each line of output.
+    fn refilter(&mut self) {
+        let query = self.input.input.to_lowercase();
+        self.filtered.clear();
+
+        for index in 0..self.runs.len() {
+            let run = &self.runs[index];
+            let matches = run.script().to_lowercase().contains(&query)
+                || run.command().to_lowercase().contains(&query)
+                || self
+                    .load_output(index)
+                    .search_lines
+                    .iter()
+                    .any(|line| line.contains(&query));
+
+            if matches {
+                self.filtered.push(index);
+            }
+        }
+
+        if self.filtered.is_empty() {
+            self.state.select(None);
+        } else {
+            self.select(0);
+        }
+    }
+
+    fn generate_runs(&self, theme: &Theme) -> Table<'static> {
+        let header = ["Started", "Script", "Exit", "Took"]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(theme.header);
+
+        let rows = self.filtered.iter().map(|&index| {
+            let run = &self.runs[index];
+            let exit = match run.exit_code() {
+                Some(0) => Span::styled("ok", theme.muted),
+                Some(code) => Span::styled(code.to_string(), theme.warning),
+                None => Span::styled("sig", theme.warning),
+            };
+
+            Row::new([
+                Cell::from(run.started_at()[5..16].to_string()),
+                Cell::from(run.script().to_string()),
+                Cell::from(exit),
+                Cell::from(Span::styled(run.duration(), theme.muted)),
+            ])
+        });
+
+        let title = if self.input.input.is_empty() {
+            format!("Runs ({})", self.runs.len())
+        } else {
+            format!("Runs ({} of {})", self.filtered.len(), self.runs.len())
+        };
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(11),
+                Constraint::Fill(1),
+                Constraint::Length(4),
+                Constraint::Length(5),
+            ],
+        )
+        .header(header)
+        .block(Block::bordered().title(title))
+        .highlight_symbol(Span::styled("█ ", theme.highlight))
+    }
+
+    /// The selected run's details and output, only showing lines containing the search if there
+    /// is one.
+    fn generate_output(&self, theme: &Theme) -> Paragraph<'static> {
+        let Some(run) = self.selected() else {
+            let message = if self.runs.is_empty() {
+                "No runs logged yet. Run a script with --log, or set log_runs = true, to keep its output here."
+            } else {
+                "No runs match the search."
+            };
+
+            return Paragraph::new(Line::styled(message, theme.muted))
+                .block(Block::bordered().title("Output"))
+                .wrap(Wrap { trim: true });
+        };
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Command: ", theme.label),
+                run.command().to_string().into(),
+            ]),
+            Line::from(vec![
+                Span::styled("Started: ", theme.label),
+                format!("{} UTC", run.started_at()).into(),
+            ]),
+            Line::from(vec![
+                Span::styled("Took: ", theme.label),
+                run.duration().into(),
+            ]),
+            Line::default(),
+        ];
+
+        let output = self.filtered[self.state.selected().unwrap_or(0)];
+        let query = self.input.input.to_lowercase();
+
+        if let Some(output) = &self.outputs[output] {
+            lines.extend(
+                output
+                    .matching_lines(&query)
+                    .map(|line| output_line(line, theme)),
+            );
+        }
+
+        let title = match (run.exit_code(), query.is_empty()) {
+            (_, false) => format!("Output: {} (matching lines)", run.script()),
+            (Some(0), true) => format!("Output: {}", run.script()),
+            (Some(code), true) => format!("Output: {} (exit {code})", run.script()),
+            (None, true) => format!("Output: {} (killed)", run.script()),
+        };
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title(title))
+            .scroll((self.scroll, 0))
+    }
+
+    /// Draws the browser over the whole frame.
+    pub fn draw(&self, frame: &mut Frame, key_map: &KeyMap, theme: &Theme) {
+        let [help_area, input_area, body_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(1),
+        ])
+        .areas(frame.area());
+        let [runs_area, output_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Fill(1)]).areas(body_area);
+
+        let key = |action: Action| {
+            let binding = key_map
+                .binding(action)
+                .map_or_else(|| "unbound".to_string(), |binding| binding.to_string());
+
+            Span::styled(binding, theme.key)
+        };
+        frame.render_widget(
+            Line::from(vec![
+                key(Action::Cancel),
+                " back  ".into(),
+                key(Action::PageUp),
+                "/".into(),
+                key(Action::PageDown),
+                " scroll output".into(),
+            ]),
+            help_area,
+        );
+
+        frame.render_widget(
+            self.input
+                .generate_input(input_area, theme)
+                .block(Block::bordered().title("Search runs and output")),
+            input_area,
+        );
+        frame.set_cursor_position(self.input.place_cursor(input_area));
+
+        let mut state = self.state.clone();
+        frame.render_stateful_widget(self.generate_runs(theme), runs_area, &mut state);
+        frame.render_widget(self.generate_output(theme), output_area);
+    }
+}
+
+/// A line of output, with its time dimmed and stderr output highlighted.
+fn output_line(line: &OutputLine, theme: &Theme) -> Line<'static> {
+    let style = match line.stream() {
+        Stream::Out => Style::new(),
+        Stream::Err => theme.warning,
+    };
+
+    if line.elapsed().is_empty() {
+        return Line::styled(line.text().to_string(), style);
+    }
+
+    Line::from(vec![
+        Span::styled(format!("{} ", line.elapsed()), theme.muted),
+        Span::styled(line.text().to_string(), style),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::{
+        fs,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    fn browser() -> (TestDir, LogBrowser) {
+        let dir = TestDir::new();
+        let logs = RunLogs::new(dir.join("logs"));
+
+        let runs = [
+            ("build", 0, "00:00.412 out compiled 12 files\n"),
+            (
+                "test",
+                1,
+                "00:01.030 out PASS src/app.test.ts\n00:02.210 err FAIL src/api.test.ts\n",
+            ),
+        ];
+
+        for (started, (script, exit_code, output)) in runs.into_iter().enumerate() {
+            let mut run = RunLog::new(
+                script.to_string(),
+                format!("npm run {script}"),
+                UNIX_EPOCH + Duration::from_millis(1_709_211_909_000 + started as u64 * 60_000),
+            );
+            run.finish(Duration::from_millis(2_500), Some(exit_code));
+            logs.save(&run).unwrap();
+            fs::write(logs.output_path(&run), output).unwrap();
+        }
+
+        (dir, LogBrowser::new(logs))
+    }
+
+    #[test]
+    fn it_new_selects_most_recent_run() {
+        let (_dir, browser) = browser();
+
+        assert_eq!(browser.selected().unwrap().script(), "test");
+        assert_eq!(browser.filtered.len(), 2);
+    }
+
+    #[test]
+    fn it_search_matches_output() {
+        let (_dir, mut browser) = browser();
+
+        browser.insert_str("compiled");
+        assert_eq!(browser.selected().unwrap().script(), "build");
+        assert_eq!(browser.filtered.len(), 1);
+
+        browser.update(Action::ClearLine);
+        browser.insert_str("nothing");
+        assert!(browser.selected().is_none());
+
+        // The time and stream before each line aren't part of the output
+        browser.update(Action::ClearLine);
+        browser.insert_str("err");
+        assert!(browser.selected().is_none());
+    }
+
+    #[test]
+    fn it_draw_shows_runs_and_output() {
+        let (_dir, mut browser) = browser();
+        browser.insert_str("src/");
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        terminal
+            .draw(|frame| browser.draw(frame, &KeyMap::default(), &Theme::default()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+}
@@ -1,5 +1,17 @@
+pub mod app;
+pub mod columns;
+pub mod confirm;
+pub mod env_editor;
+pub mod event;
 pub mod help;
+pub mod history;
 pub mod input;
+pub mod keymap;
 pub mod layout;
-pub mod render;
+pub mod log_browser;
+pub mod preview;
 pub mod table;
+pub mod terminal;
+pub mod theme;
+pub mod tree;
+pub mod vim;
@@ -0,0 +1,98 @@
+use crate::ui::table::ScriptTable;
+use crate::ui::theme::Theme;
+use ratatui::{
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+
+/// Details of the selected script, including its full name and command as the table may have
+/// cut them short.
+pub fn generate_preview(script_table: &ScriptTable, theme: &Theme) -> Paragraph<'static> {
+    let name = script_table.get_script_name();
+
+    let lines = match &name {
+        Some(name) => vec![
+            Line::from(vec![
+                Span::styled("Command: ", theme.label),
+                script_table
+                    .scripts
+                    .command(name)
+                    .unwrap_or_default()
+                    .to_string()
+                    .into(),
+            ]),
+            Line::from(vec![
+                Span::styled("Description: ", theme.label),
+                script_table
+                    .scripts
+                    .description(name)
+                    .unwrap_or("none")
+                    .to_string()
+                    .into(),
+            ]),
+            relation_line("Calls", script_table.graph.callees(name), theme),
+            relation_line("Called by", script_table.graph.callers(name), theme),
+        ],
+        None => vec![],
+    };
+
+    let title = match name {
+        Some(name) => format!("Details: {name}"),
+        None => "Details".to_string(),
+    };
+
+    Paragraph::new(lines).block(Block::bordered().title(title))
+}
+
+fn relation_line(label: &str, scripts: Vec<&str>, theme: &Theme) -> Line<'static> {
+    let scripts = if scripts.is_empty() {
+        "none".to_string()
+    } else {
+        scripts.join(", ")
+    };
+
+    Line::from(vec![
+        Span::styled(format!("{label}: "), theme.label),
+        scripts.into(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::layout::generate_layout;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+    use scriptor::Scripts;
+    use std::collections::HashMap;
+
+    #[test]
+    fn it_generate_preview_shows_description_and_relations() {
+        let mut script_table = ScriptTable::new();
+        script_table.reload(
+            Scripts::from(HashMap::from([
+                ("ci".to_string(), "yarn lint && yarn test".to_string()),
+                ("lint".to_string(), "eslint .".to_string()),
+                ("test".to_string(), "jest".to_string()),
+                ("verify".to_string(), "npm run lint".to_string()),
+            ]))
+            .with_descriptions(HashMap::from([(
+                "lint".to_string(),
+                "Checks code style".to_string(),
+            )])),
+        );
+        script_table.state.select(Some(1));
+        let preview = generate_preview(&script_table, &Theme::default());
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| {
+                let [_, _, _, preview_area] = generate_layout(frame.area());
+
+                frame.render_widget(&preview, preview_area)
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+}
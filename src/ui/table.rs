@@ -1,37 +1,99 @@
-use crate::file_reader::scripts::Scripts;
-use ratatui::widgets::TableState;
+use crate::ui::columns::{ellipsize, Columns};
+use crate::ui::theme::Theme;
+use crate::ui::tree::{build_tree, group_label, TableRow};
+use ratatui::widgets::{ScrollbarState, TableState};
 use ratatui::{
-    layout::Constraint,
-    style::{Style, Stylize},
-    text::{Span, Text},
+    layout::{Constraint, Rect},
+    style::Style,
+    text::{Line, Span, Text},
     widgets::{Block, Cell, Row, Table},
 };
-use std::collections::HashMap;
+use scriptor::{DependencyGraph, Scripts, SortOrder};
+use std::collections::{HashMap, HashSet};
+
+const PIN_MARKER: &str = "★";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TableView {
+    Flat,
+    Tree,
+}
 
 pub struct ScriptTable {
     pub state: TableState,
     pub scripts: Scripts,
     pub filtered: HashMap<String, String>,
+    pub graph: DependencyGraph,
+    pub pinned: HashSet<String>,
+    pub view: TableView,
+    pub sort: SortOrder,
+    recent: Vec<String>,
+    expanded: HashSet<String>,
+    query: String,
+}
+
+/// The scripts tests build tables from, so they don't depend on any package.json.
+#[cfg(test)]
+pub fn sample_scripts() -> Scripts {
+    Scripts::from(HashMap::from(
+        [
+            ("android", "yarn android"),
+            ("ios", "yarn ios"),
+            ("run", "expo start"),
+            ("start", "yarn start"),
+            ("test", "jest"),
+        ]
+        .map(|(name, command)| (name.to_string(), command.to_string())),
+    ))
 }
 
 impl ScriptTable {
+    #[cfg(test)]
     pub fn new() -> Self {
-        let scripts = Scripts::new();
-        let temp = scripts.scripts.clone();
+        Self::from_scripts(sample_scripts())
+    }
+
+    pub fn from_scripts(scripts: Scripts) -> Self {
+        let temp = scripts.commands().clone();
         let mut keys: Vec<_> = temp.keys().collect();
         keys.sort();
 
+        let graph = DependencyGraph::from_scripts(scripts.commands());
+
         Self {
             state: TableState::default().with_selected(0),
             scripts,
             filtered: temp,
+            graph,
+            pinned: HashSet::new(),
+            view: TableView::Flat,
+            sort: SortOrder::Name,
+            recent: vec![],
+            expanded: HashSet::new(),
+            query: String::new(),
         }
     }
 
-    fn generate_table_header(&self) -> Row<'static> {
-        let header_style = Style::default().on_black();
+    pub fn with_pinned(mut self, pinned: Vec<String>) -> Self {
+        self.pinned = pinned.into_iter().collect();
+        self
+    }
+
+    /// Sets the order of unpinned scripts, where `recent` lists scripts most recently run first.
+    pub fn with_sort(mut self, sort: SortOrder, recent: Vec<String>) -> Self {
+        self.sort = sort;
+        self.recent = recent;
+        self
+    }
+
+    fn generate_table_header(&self, columns: Columns, theme: &Theme) -> Row<'static> {
+        let header_style = theme.header;
+        let titles = match columns {
+            Columns::Wide { .. } => vec!["", "Script", "Command"],
+            Columns::Narrow { .. } => vec!["", "Script / Command"],
+        };
 
-        ["Script", "Command"]
+        titles
             .into_iter()
             .map(Cell::from)
             .collect::<Row>()
@@ -40,9 +102,14 @@ impl ScriptTable {
     }
 
     pub fn next(&mut self) {
+        let row_count = self.rows().len();
+        if row_count == 0 {
+            return;
+        }
+
         let i = match self.state.selected() {
             Some(i) => {
-                if i >= self.filtered.len() - 1 {
+                if i >= row_count - 1 {
                     0
                 } else {
                     i + 1
@@ -51,14 +118,18 @@ impl ScriptTable {
             None => 0,
         };
         self.state.select(Some(i));
-        //self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
     }
 
     pub fn previous(&mut self) {
+        let row_count = self.rows().len();
+        if row_count == 0 {
+            return;
+        }
+
         let i = match self.state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.filtered.len() - 1
+                    row_count - 1
                 } else {
                     i - 1
                 }
@@ -66,68 +137,365 @@ impl ScriptTable {
             None => 0,
         };
         self.state.select(Some(i));
-        //self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
+    }
+
+    pub fn select_first(&mut self) {
+        self.state.select(Some(0));
+    }
+
+    pub fn select_last(&mut self) {
+        self.state.select(Some(self.rows().len().saturating_sub(1)));
+    }
+
+    /// Moves the selection by `offset` rows, stopping at the first and last row.
+    pub fn move_selection(&mut self, offset: isize) {
+        let last = self.rows().len().saturating_sub(1);
+        let current = self.state.selected().unwrap_or(0);
+
+        self.state
+            .select(Some(current.saturating_add_signed(offset).min(last)));
+    }
+
+    /// Selects the row at `index` in the list of rows, if there is one.
+    pub fn select_index(&mut self, index: usize) -> bool {
+        let exists = index < self.rows().len();
+        if exists {
+            self.state.select(Some(index));
+        }
+
+        exists
+    }
+
+    /// The index of the row drawn on screen line `y` when the table is drawn in `area`.
+    pub fn row_at(&self, area: Rect, y: u16) -> Option<usize> {
+        // Skip the top border and the header
+        let first_row = area.y + 2;
+        if y < first_row || y >= area.bottom().saturating_sub(1) {
+            return None;
+        }
+
+        let row_height = self.columns(area.width).row_height();
+        let index = self.state.offset() + ((y - first_row) / row_height) as usize;
+
+        (index < self.rows().len()).then_some(index)
     }
 
     pub fn filter_results(&mut self, input: String) {
-        self.filtered = self
-            .scripts
-            .scripts
-            .clone()
-            .into_iter()
-            .filter(|(key, _value)| key.contains(&input))
-            .collect();
+        self.filtered = self.scripts.filter(&input);
+        self.query = input;
     }
 
-    pub fn get_script_name(&self) -> String {
-        let index = self.state.selected().unwrap();
+    /// Swaps in a freshly read set of scripts, reapplying the current search and keeping the
+    /// selected script selected if it still exists.
+    pub fn reload(&mut self, scripts: Scripts) {
+        let selected = self.selected_row();
 
-        let mut keys: Vec<String> = self.filtered.clone().into_keys().collect();
-        keys.sort();
+        self.graph = DependencyGraph::from_scripts(scripts.commands());
+        self.scripts = scripts;
+        self.filter_results(self.query.clone());
+
+        if !selected.is_some_and(|row| self.select_row(&row)) {
+            let last = self.rows().len().saturating_sub(1);
+            let index = self.state.selected().unwrap_or(0).min(last);
+
+            self.state.select(Some(index));
+        }
+    }
+
+    pub fn toggle_pin(&mut self) {
+        let Some(script_name) = self.get_script_name() else {
+            return;
+        };
+
+        if !self.pinned.remove(&script_name) {
+            self.pinned.insert(script_name.clone());
+        }
+
+        // Pinning reorders the rows, so keep the selection on the script that was toggled
+        self.select_script(&script_name);
+    }
+
+    pub fn toggle_view(&mut self) {
+        let selected = self.get_script_name();
+
+        self.view = match self.view {
+            TableView::Flat => TableView::Tree,
+            TableView::Tree => TableView::Flat,
+        };
+
+        if !selected.is_some_and(|name| self.select_script(&name)) {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn expand(&mut self) {
+        if let Some(TableRow::Group {
+            path,
+            expanded: false,
+            ..
+        }) = self.selected_row()
+        {
+            self.expanded.insert(path);
+        }
+    }
+
+    /// Collapses the selected group, or the group containing the selected script.
+    pub fn collapse(&mut self) {
+        let path = match self.selected_row() {
+            Some(TableRow::Group {
+                path,
+                expanded: true,
+                ..
+            }) => path,
+            Some(TableRow::Script {
+                group: Some(group), ..
+            }) => group,
+            _ => return,
+        };
+
+        self.expanded.remove(&path);
+        self.select_row(&TableRow::Group {
+            path,
+            depth: 0,
+            size: 0,
+            expanded: false,
+        });
+    }
+
+    /// Expands or collapses the selected group, returning false if a script is selected.
+    pub fn toggle_group(&mut self) -> bool {
+        match self.selected_row() {
+            Some(TableRow::Group { expanded, .. }) => {
+                if expanded {
+                    self.collapse();
+                } else {
+                    self.expand();
+                }
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn pinned_scripts(&self) -> Vec<String> {
+        let mut pinned: Vec<String> = self.pinned.iter().cloned().collect();
+        pinned.sort();
+
+        pinned
+    }
+
+    /// Returns the filtered script names in display order: pinned scripts first, then the rest,
+    /// each alphabetically or by how recently they were run.
+    fn sorted_keys(&self) -> Vec<&String> {
+        let mut keys: Vec<&String> = self.filtered.keys().collect();
+        keys.sort_by_key(|key| (!self.pinned.contains(*key), self.recency(key), *key));
+
+        keys
+    }
 
-        keys.get(index).unwrap().to_string()
+    fn recency(&self, script: &str) -> usize {
+        match self.sort {
+            SortOrder::Name => 0,
+            SortOrder::Recent => self
+                .recent
+                .iter()
+                .position(|recent| recent == script)
+                .unwrap_or(usize::MAX),
+        }
+    }
+
+    /// Returns the rows currently shown. In the tree view pinned scripts stay at the top and the
+    /// rest are grouped by prefix, with every group expanded while searching so matches show.
+    pub fn rows(&self) -> Vec<TableRow> {
+        let keys = self.sorted_keys();
+        let script_row = |name: &String| TableRow::Script {
+            name: name.to_string(),
+            depth: 0,
+            group: None,
+        };
+
+        match self.view {
+            TableView::Flat => keys.into_iter().map(script_row).collect(),
+            TableView::Tree => {
+                let (pinned, rest): (Vec<&String>, Vec<&String>) =
+                    keys.into_iter().partition(|key| self.pinned.contains(*key));
+
+                let mut rows: Vec<TableRow> = pinned.into_iter().map(script_row).collect();
+                rows.extend(build_tree(&rest, &self.expanded, !self.query.is_empty()));
+
+                rows
+            }
+        }
+    }
+
+    pub fn selected_row(&self) -> Option<TableRow> {
+        self.rows().get(self.state.selected()?).cloned()
+    }
+
+    /// Selects the row for the same script or group as `target`, returning whether it is shown.
+    fn select_row(&mut self, target: &TableRow) -> bool {
+        let index = self.rows().iter().position(|row| match (row, target) {
+            (TableRow::Script { name, .. }, TableRow::Script { name: target, .. }) => {
+                name == target
+            }
+            (TableRow::Group { path, .. }, TableRow::Group { path: target, .. }) => path == target,
+            _ => false,
+        });
+
+        if index.is_some() {
+            self.state.select(index);
+        }
+
+        index.is_some()
+    }
+
+    fn select_script(&mut self, name: &str) -> bool {
+        self.select_row(&TableRow::Script {
+            name: name.to_string(),
+            depth: 0,
+            group: None,
+        })
     }
 
-    fn generate_table_rows(&self) -> Vec<Row<'static>> {
-        let mut script_iter: Vec<_> = self.filtered.iter().collect();
-        script_iter.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+    pub fn get_script_name(&self) -> Option<String> {
+        match self.selected_row()? {
+            TableRow::Script { name, .. } => Some(name),
+            TableRow::Group { .. } => None,
+        }
+    }
 
-        let script_rows = script_iter
-            .iter()
-            .map(|(key, value)| {
-                let content = [
-                    Cell::from(Span::raw(key.to_string())),
-                    Cell::from(Span::raw(value.to_string())),
-                ];
+    /// The name and command shown for each row, with names indented by their tree depth.
+    fn row_cells(&self) -> Vec<(TableRow, String, String)> {
+        self.rows()
+            .into_iter()
+            .map(|row| {
+                let (name, command) = match &row {
+                    TableRow::Script { name, depth, .. } => (
+                        format!("{}{name}", "  ".repeat(*depth)),
+                        self.filtered[name].to_string(),
+                    ),
+                    TableRow::Group {
+                        path,
+                        depth,
+                        size,
+                        expanded,
+                    } => {
+                        let arrow = if *expanded { "▾" } else { "▸" };
+
+                        (
+                            format!("{}{arrow} {}", "  ".repeat(*depth), group_label(path)),
+                            format!("{size} scripts"),
+                        )
+                    }
+                };
+
+                (row, name, command)
+            })
+            .collect()
+    }
+
+    /// How the columns are laid out for a table drawn `width` characters wide.
+    pub fn columns(&self, width: u16) -> Columns {
+        let cells = self.row_cells();
+
+        Columns::fit(cells.iter().map(|(_, name, _)| name.as_str()), width)
+    }
 
-                Row::new(content)
+    fn generate_table_rows(&self, columns: Columns, theme: &Theme) -> Vec<Row<'static>> {
+        let script_rows = self
+            .row_cells()
+            .into_iter()
+            .map(|(row, name, command)| {
+                let (marker, name_style, command_style) = match &row {
+                    TableRow::Script { name, .. } if self.pinned.contains(name) => {
+                        (PIN_MARKER, Style::new(), Style::new())
+                    }
+                    TableRow::Script { .. } => ("", Style::new(), Style::new()),
+                    TableRow::Group { .. } => ("", theme.group, theme.muted),
+                };
+                let marker = Cell::from(Span::styled(marker, theme.pin));
+
+                match columns {
+                    Columns::Wide {
+                        name: name_width,
+                        command: command_width,
+                    } => Row::new([
+                        marker,
+                        Cell::from(Span::styled(ellipsize(&name, name_width), name_style)),
+                        Cell::from(Span::styled(
+                            ellipsize(&command, command_width),
+                            command_style,
+                        )),
+                    ]),
+                    Columns::Narrow { width } => Row::new([
+                        marker,
+                        Cell::from(Text::from(vec![
+                            Line::styled(ellipsize(&name, width), name_style),
+                            Line::styled(
+                                format!("  {}", ellipsize(&command, width.saturating_sub(2))),
+                                command_style.patch(theme.muted),
+                            ),
+                        ])),
+                    ])
+                    .height(2),
+                }
             })
             .collect::<Vec<Row>>();
 
         script_rows
     }
 
-    pub fn generate_table(&self) -> Table<'static> {
+    /// The table title, counting the selected row among the rows shown.
+    fn title(&self) -> String {
+        let row_count = self.rows().len();
+        let position = match self.state.selected() {
+            Some(selected) if row_count > 0 => selected + 1,
+            _ => 0,
+        };
+
+        format!("Scripts ({position} of {row_count})")
+    }
+
+    /// Scrollbar state following the selected row, for tables too long to show at once.
+    pub fn scrollbar_state(&self, visible_rows: usize) -> Option<ScrollbarState> {
+        let row_count = self.rows().len();
+        if row_count <= visible_rows {
+            return None;
+        }
+
+        Some(
+            ScrollbarState::new(row_count)
+                .position(self.state.selected().unwrap_or(0))
+                .viewport_content_length(visible_rows),
+        )
+    }
+
+    /// Builds the table to draw in `area`, fitting the columns to its width.
+    pub fn generate_table(&self, area: Rect, theme: &Theme) -> Table<'static> {
         let bar = " █ ";
+        let columns = self.columns(area.width);
 
-        let header = self.generate_table_header();
+        let header = self.generate_table_header(columns, theme);
 
-        let script_rows = self.generate_table_rows();
+        let script_rows = self.generate_table_rows(columns, theme);
 
-        let scripts_table = Table::new(
-            script_rows,
-            [
-                Constraint::Length(25 + 1),
-                Constraint::Min(25 + 1),
-                Constraint::Min(25),
+        let widths = match columns {
+            Columns::Wide { name, .. } => vec![
+                Constraint::Length(1),
+                Constraint::Length(name as u16),
+                Constraint::Fill(1),
             ],
-        )
-        .header(header)
-        .block(Block::bordered().title("Scripts"))
-        .highlight_symbol(
-            Text::from(vec![bar.into(), bar.into(), bar.into(), bar.into()])
-                .style(Style::new().red()),
-        );
+            Columns::Narrow { .. } => vec![Constraint::Length(1), Constraint::Fill(1)],
+        };
+
+        let scripts_table = Table::new(script_rows, widths)
+            .header(header)
+            .block(Block::bordered().title(self.title()))
+            .highlight_symbol(
+                Text::from(vec![bar.into(), bar.into(), bar.into(), bar.into()])
+                    .style(theme.highlight),
+            );
 
         scripts_table
     }
@@ -153,7 +521,7 @@ mod tests {
             ("start".to_string(), "yarn start".to_string()),
         ]);
 
-        assert_eq!(script_table.scripts.scripts, expected);
+        assert_eq!(script_table.scripts.commands(), &expected);
         assert_eq!(script_table.filtered, expected);
         assert_eq!(script_table.state.selected(), Some(0));
     }
@@ -163,11 +531,11 @@ mod tests {
         let mut script_table = ScriptTable::new();
 
         let result = script_table.get_script_name();
-        assert_eq!(result, "android");
+        assert_eq!(result, Some("android".to_string()));
 
         script_table.state.select(Some(1));
         let result2 = script_table.get_script_name();
-        assert_eq!(result2, "ios");
+        assert_eq!(result2, Some("ios".to_string()));
     }
 
     #[test]
@@ -191,6 +559,27 @@ mod tests {
         assert_eq!(script_table.filtered, expected_after);
     }
 
+    #[test]
+    fn it_filter_results_matches_descriptions() {
+        let mut script_table = ScriptTable::new();
+        script_table.reload(
+            Scripts::from(HashMap::from([
+                ("build".to_string(), "tsc".to_string()),
+                ("lint".to_string(), "eslint .".to_string()),
+            ]))
+            .with_descriptions(HashMap::from([(
+                "build".to_string(),
+                "Compiles the TypeScript sources".to_string(),
+            )])),
+        );
+
+        script_table.filter_results(String::from("TypeScript"));
+
+        let expected = HashMap::from([("build".to_string(), "tsc".to_string())]);
+
+        assert_eq!(script_table.filtered, expected);
+    }
+
     #[test]
     fn it_previous_correctly_selects_previous_option() {
         let mut script_table = ScriptTable::new();
@@ -234,16 +623,316 @@ mod tests {
         assert_eq!(script_table.state.selected(), Some(0));
     }
 
+    #[test]
+    fn it_toggle_pin_moves_script_to_top_and_keeps_selection() {
+        let mut script_table = ScriptTable::new();
+        script_table.state.select(Some(3));
+
+        script_table.toggle_pin();
+
+        assert_eq!(script_table.pinned_scripts(), vec!["start".to_string()]);
+        assert_eq!(script_table.state.selected(), Some(0));
+        assert_eq!(script_table.get_script_name(), Some("start".to_string()));
+    }
+
+    #[test]
+    fn it_toggle_pin_unpins_pinned_script() {
+        let mut script_table = ScriptTable::new().with_pinned(vec!["start".to_string()]);
+
+        script_table.toggle_pin();
+
+        assert!(script_table.pinned.is_empty());
+        assert_eq!(script_table.get_script_name(), Some("start".to_string()));
+        assert_eq!(script_table.state.selected(), Some(3));
+    }
+
+    #[test]
+    fn it_pinned_scripts_stay_on_top_when_filtering() {
+        let mut script_table = ScriptTable::new().with_pinned(vec!["test".to_string()]);
+
+        script_table.filter_results(String::from("t"));
+
+        assert_eq!(
+            script_table.sorted_keys(),
+            vec![&"test".to_string(), &"start".to_string()]
+        );
+    }
+
+    #[test]
+    fn it_recent_sort_orders_by_last_run() {
+        let script_table = ScriptTable::new()
+            .with_pinned(vec!["test".to_string()])
+            .with_sort(
+                SortOrder::Recent,
+                vec!["start".to_string(), "ios".to_string()],
+            );
+
+        assert_eq!(
+            script_table.sorted_keys(),
+            vec![
+                &"test".to_string(),
+                &"start".to_string(),
+                &"ios".to_string(),
+                &"android".to_string(),
+                &"run".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reload_keeps_query_and_selected_script() {
+        let mut script_table = ScriptTable::new();
+        script_table.filter_results(String::from("t"));
+        script_table.state.select(Some(1));
+
+        script_table.reload(Scripts::from(HashMap::from([
+            ("lint".to_string(), "eslint .".to_string()),
+            ("start".to_string(), "yarn start".to_string()),
+            ("test".to_string(), "jest".to_string()),
+        ])));
+
+        assert_eq!(
+            script_table.sorted_keys(),
+            vec![
+                &"lint".to_string(),
+                &"start".to_string(),
+                &"test".to_string()
+            ]
+        );
+        assert_eq!(script_table.get_script_name(), Some("test".to_string()));
+    }
+
+    #[test]
+    fn it_reload_clamps_selection_when_selected_script_is_removed() {
+        let mut script_table = ScriptTable::new();
+        script_table.state.select(Some(4));
+
+        script_table.reload(Scripts::from(HashMap::from([(
+            "build".to_string(),
+            "tsc".to_string(),
+        )])));
+
+        assert_eq!(script_table.state.selected(), Some(0));
+        assert_eq!(script_table.get_script_name(), Some("build".to_string()));
+    }
+
+    #[test]
+    fn it_move_selection_stops_at_ends() {
+        let mut script_table = ScriptTable::new();
+
+        script_table.move_selection(3);
+        assert_eq!(script_table.state.selected(), Some(3));
+
+        script_table.move_selection(10);
+        assert_eq!(script_table.state.selected(), Some(4));
+
+        script_table.move_selection(-10);
+        assert_eq!(script_table.state.selected(), Some(0));
+    }
+
+    #[test]
+    fn it_row_at_maps_screen_lines_to_rows() {
+        let mut script_table = ScriptTable::new();
+        let area = Rect::new(0, 4, 80, 8);
+
+        assert_eq!(script_table.row_at(area, 5), None);
+        assert_eq!(script_table.row_at(area, 6), Some(0));
+        assert_eq!(script_table.row_at(area, 8), Some(2));
+        assert_eq!(script_table.row_at(area, 11), None);
+
+        *script_table.state.offset_mut() = 1;
+        assert_eq!(script_table.row_at(area, 6), Some(1));
+        assert_eq!(script_table.row_at(area, 10), None);
+    }
+
+    #[test]
+    fn it_title_counts_selected_row() {
+        let mut script_table = ScriptTable::new();
+        script_table.next();
+
+        assert_eq!(script_table.title(), "Scripts (2 of 5)");
+
+        script_table.filter_results("nothing matches".to_string());
+        assert_eq!(script_table.title(), "Scripts (0 of 0)");
+    }
+
+    #[test]
+    fn it_scrollbar_state_only_when_rows_overflow() {
+        let script_table = ScriptTable::new();
+
+        assert!(script_table.scrollbar_state(5).is_none());
+        assert!(script_table.scrollbar_state(3).is_some());
+    }
+
+    #[test]
+    fn it_select_index_ignores_missing_rows() {
+        let mut script_table = ScriptTable::new();
+
+        assert!(script_table.select_index(2));
+        assert!(!script_table.select_index(10));
+        assert_eq!(script_table.state.selected(), Some(2));
+    }
+
+    #[test]
+    fn it_select_first_and_last() {
+        let mut script_table = ScriptTable::new();
+
+        script_table.select_last();
+        assert_eq!(script_table.get_script_name(), Some("test".to_string()));
+
+        script_table.select_first();
+        assert_eq!(script_table.get_script_name(), Some("android".to_string()));
+    }
+
+    #[test]
+    fn it_next_and_previous_do_nothing_without_rows() {
+        let mut script_table = ScriptTable::new();
+        script_table.filter_results(String::from("no such script"));
+
+        script_table.next();
+        script_table.previous();
+
+        assert_eq!(script_table.state.selected(), Some(0));
+        assert_eq!(script_table.get_script_name(), None);
+    }
+
+    fn tree_table() -> ScriptTable {
+        let mut script_table = ScriptTable::new();
+        script_table.reload(Scripts::from(HashMap::from([
+            ("build:ios".to_string(), "expo build:ios".to_string()),
+            ("build:web".to_string(), "expo build:web".to_string()),
+            ("lint".to_string(), "eslint .".to_string()),
+        ])));
+        script_table.toggle_view();
+
+        script_table
+    }
+
+    #[test]
+    fn it_toggle_view_groups_scripts_by_prefix() {
+        let script_table = tree_table();
+
+        assert_eq!(script_table.view, TableView::Tree);
+        assert_eq!(script_table.rows().len(), 2);
+        assert_eq!(script_table.get_script_name(), None);
+    }
+
+    #[test]
+    fn it_toggle_group_expands_and_collapses_selected_group() {
+        let mut script_table = tree_table();
+
+        assert!(script_table.toggle_group());
+        assert_eq!(script_table.rows().len(), 4);
+
+        script_table.next();
+        assert_eq!(
+            script_table.get_script_name(),
+            Some("build:ios".to_string())
+        );
+
+        script_table.collapse();
+        assert_eq!(script_table.rows().len(), 2);
+        assert_eq!(script_table.state.selected(), Some(0));
+        assert_eq!(
+            script_table.selected_row(),
+            Some(TableRow::Group {
+                path: "build".to_string(),
+                depth: 0,
+                size: 2,
+                expanded: false,
+            })
+        );
+    }
+
+    #[test]
+    fn it_tree_view_expands_groups_while_searching() {
+        let mut script_table = tree_table();
+
+        script_table.filter_results(String::from("build"));
+
+        assert_eq!(script_table.rows().len(), 3);
+        script_table.next();
+        assert_eq!(
+            script_table.get_script_name(),
+            Some("build:ios".to_string())
+        );
+    }
+
+    #[test]
+    fn it_generate_table_renders_tree_view() {
+        let mut script_table = tree_table();
+        script_table.expand();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                let [_, _, table_area, _] = generate_layout(frame.area());
+                let table = script_table.generate_table(table_area, &Theme::default());
+
+                frame.render_widget(&table, table_area)
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn it_generate_table_renders_pinned_scripts_first() {
+        let script_table = ScriptTable::new().with_pinned(vec!["run".to_string()]);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                let [_, _, table_area, _] = generate_layout(frame.area());
+                let table = script_table.generate_table(table_area, &Theme::default());
+
+                frame.render_widget(&table, table_area)
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn it_generate_table_stacks_columns_when_narrow() {
+        let script_table = ScriptTable::new();
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                let [_, _, table_area, _] = generate_layout(frame.area());
+                let table = script_table.generate_table(table_area, &Theme::default());
+
+                frame.render_stateful_widget(&table, table_area, &mut script_table.state.clone())
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn it_row_at_accounts_for_stacked_rows() {
+        let script_table = ScriptTable::new();
+        let area = Rect::new(0, 0, 40, 12);
+
+        assert_eq!(script_table.row_at(area, 2), Some(0));
+        assert_eq!(script_table.row_at(area, 3), Some(0));
+        assert_eq!(script_table.row_at(area, 4), Some(1));
+    }
+
     #[test]
     fn it_generate_table_renders_correctly() {
         let script_table = ScriptTable::new();
-        let table = script_table.generate_table();
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
 
         terminal
             .draw(|frame| {
-                let [_, _, table_area] = generate_layout(frame.area());
+                let [_, _, table_area, _] = generate_layout(frame.area());
+                let table = script_table.generate_table(table_area, &Theme::default());
 
                 frame.render_widget(&table, table_area)
             })
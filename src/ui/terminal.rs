@@ -0,0 +1,43 @@
+use crossterm::{
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::io::{self, stderr, Stderr};
+
+pub type Tui = Terminal<CrosstermBackend<Stderr>>;
+
+/// Sets the terminal up like `ratatui::init`, but draws on stderr so that stdout stays free for
+/// `--print`, and turns on mouse and bracketed paste events.
+pub fn init() -> io::Result<Tui> {
+    set_panic_hook();
+    enable_raw_mode()?;
+    execute!(
+        stderr(),
+        EnterAlternateScreen,
+        EnableBracketedPaste,
+        EnableMouseCapture
+    )?;
+
+    Terminal::new(CrosstermBackend::new(stderr()))
+}
+
+pub fn restore() {
+    // Raw mode goes first as it has more side effects than leaving the alternate screen
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        stderr(),
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    );
+}
+
+fn set_panic_hook() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore();
+        hook(info);
+    }));
+}
@@ -0,0 +1,265 @@
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use serde::Deserialize;
+use std::{fs, path::Path, str::FromStr};
+
+const PRESETS: [&str; 3] = ["dark", "light", "high-contrast"];
+
+const MODIFIERS: [(&str, Modifier); 6] = [
+    ("bold", Modifier::BOLD),
+    ("dim", Modifier::DIM),
+    ("italic", Modifier::ITALIC),
+    ("underlined", Modifier::UNDERLINED),
+    ("reversed", Modifier::REVERSED),
+    ("crossed_out", Modifier::CROSSED_OUT),
+];
+
+/// The styles every widget is drawn with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub input: Style,
+    pub header: Style,
+    pub highlight: Style,
+    pub pin: Style,
+    pub group: Style,
+    pub muted: Style,
+    pub key: Style,
+    pub label: Style,
+    pub warning: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// A theme file: any style left out is taken from the `base` preset.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    input: Option<StyleSpec>,
+    header: Option<StyleSpec>,
+    highlight: Option<StyleSpec>,
+    pin: Option<StyleSpec>,
+    group: Option<StyleSpec>,
+    muted: Option<StyleSpec>,
+    key: Option<StyleSpec>,
+    label: Option<StyleSpec>,
+    warning: Option<StyleSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleSpec {
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    modifiers: Vec<String>,
+}
+
+impl StyleSpec {
+    fn to_style(&self) -> Result<Style> {
+        let parse_color = |color: &str| {
+            Color::from_str(color).map_err(|_| eyre!("Invalid colour `{color}` in theme"))
+        };
+
+        let mut style = Style::default();
+
+        if let Some(fg) = &self.fg {
+            style = style.fg(parse_color(fg)?);
+        }
+        if let Some(bg) = &self.bg {
+            style = style.bg(parse_color(bg)?);
+        }
+        for name in &self.modifiers {
+            let (_, modifier) = MODIFIERS
+                .iter()
+                .find(|(modifier, _)| modifier == name)
+                .ok_or_else(|| eyre!("Invalid modifier `{name}` in theme"))?;
+            style = style.add_modifier(*modifier);
+        }
+
+        Ok(style)
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            input: Style::new().yellow(),
+            header: Style::new().on_black(),
+            highlight: Style::new().red(),
+            pin: Style::new().yellow(),
+            group: Style::new().bold(),
+            muted: Style::new().dim(),
+            key: Style::new().bold(),
+            label: Style::new().bold(),
+            warning: Style::new().red().bold(),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            input: Style::new().blue(),
+            header: Style::new().black().on_gray(),
+            highlight: Style::new().magenta(),
+            pin: Style::new().magenta(),
+            group: Style::new().bold(),
+            muted: Style::new().dark_gray(),
+            key: Style::new().blue().bold(),
+            label: Style::new().bold(),
+            warning: Style::new().red().bold(),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            input: Style::new().white().bold(),
+            header: Style::new().black().on_white().bold(),
+            highlight: Style::new().yellow().bold(),
+            pin: Style::new().yellow().bold(),
+            group: Style::new().white().bold().underlined(),
+            muted: Style::new().white(),
+            key: Style::new().yellow().bold(),
+            label: Style::new().white().bold(),
+            warning: Style::new().light_red().bold().reversed(),
+        }
+    }
+
+    /// Resolves the `theme` config setting, which is either a preset name or a path to a theme
+    /// file. Colours are dropped when `NO_COLOR` is set.
+    pub fn load(theme: Option<&str>) -> Result<Self> {
+        let theme = match theme {
+            None => Self::default(),
+            Some(name) if PRESETS.contains(&name) => Self::preset(name),
+            Some(path) if path.ends_with(".toml") => Self::from_file(Path::new(path))?,
+            Some(name) => {
+                return Err(eyre!(
+                    "Unknown theme `{name}`, expected one of {} or a path to a .toml theme file",
+                    PRESETS.join(", ")
+                ))
+            }
+        };
+
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+        Ok(if no_color {
+            theme.without_colors()
+        } else {
+            theme
+        })
+    }
+
+    fn preset(name: &str) -> Self {
+        match name {
+            "light" => Self::light(),
+            "high-contrast" => Self::high_contrast(),
+            _ => Self::dark(),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Could not read theme file {}", path.display()))?;
+
+        Self::parse(&contents).wrap_err_with(|| format!("Invalid theme file {}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let file: ThemeFile = toml::from_str(contents).map_err(|error| eyre!("{error}"))?;
+
+        let base = match file.base.as_deref() {
+            None => Self::default(),
+            Some(name) if PRESETS.contains(&name) => Self::preset(name),
+            Some(name) => return Err(eyre!("Unknown base theme `{name}`")),
+        };
+
+        let style = |spec: Option<StyleSpec>, base: Style| match spec {
+            Some(spec) => spec.to_style(),
+            None => Ok(base),
+        };
+
+        Ok(Self {
+            input: style(file.input, base.input)?,
+            header: style(file.header, base.header)?,
+            highlight: style(file.highlight, base.highlight)?,
+            pin: style(file.pin, base.pin)?,
+            group: style(file.group, base.group)?,
+            muted: style(file.muted, base.muted)?,
+            key: style(file.key, base.key)?,
+            label: style(file.label, base.label)?,
+            warning: style(file.warning, base.warning)?,
+        })
+    }
+
+    /// Keeps only the modifiers of each style, for terminals asked not to show colour.
+    pub fn without_colors(self) -> Self {
+        let strip = |style: Style| Style {
+            fg: None,
+            bg: None,
+            ..style
+        };
+
+        Self {
+            input: strip(self.input),
+            header: strip(self.header),
+            highlight: strip(self.highlight),
+            pin: strip(self.pin),
+            group: strip(self.group),
+            muted: strip(self.muted),
+            key: strip(self.key),
+            label: strip(self.label),
+            warning: strip(self.warning),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_load_resolves_presets() {
+        assert_eq!(Theme::preset("light"), Theme::light());
+        assert_eq!(Theme::preset("high-contrast"), Theme::high_contrast());
+        assert!(Theme::load(Some("solarized")).is_err());
+    }
+
+    #[test]
+    fn it_parse_layers_styles_over_base() {
+        let theme = Theme::parse(
+            r##"
+            base = "light"
+            input = { fg = "#ff8800", modifiers = ["bold"] }
+            highlight = { fg = "green", bg = "black" }
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(theme.input, Style::new().fg(Color::Rgb(255, 136, 0)).bold());
+        assert_eq!(theme.highlight, Style::new().green().on_black());
+        assert_eq!(theme.header, Theme::light().header);
+    }
+
+    #[test]
+    fn it_parse_rejects_invalid_styles() {
+        let colour = Theme::parse(r#"input = { fg = "not-a-colour" }"#).unwrap_err();
+        let modifier = Theme::parse(r#"input = { modifiers = ["blink"] }"#).unwrap_err();
+
+        assert_eq!(colour.to_string(), "Invalid colour `not-a-colour` in theme");
+        assert_eq!(modifier.to_string(), "Invalid modifier `blink` in theme");
+    }
+
+    #[test]
+    fn it_without_colors_keeps_modifiers() {
+        let theme = Theme::high_contrast().without_colors();
+
+        assert_eq!(theme.header, Style::new().bold());
+        assert_eq!(theme.input, Style::new().bold());
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, HashSet};
+
+const SEPARATORS: [char; 3] = [':', '.', '/'];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TableRow {
+    Group {
+        path: String,
+        depth: usize,
+        size: usize,
+        expanded: bool,
+    },
+    Script {
+        name: String,
+        depth: usize,
+        group: Option<String>,
+    },
+}
+
+enum Entry<'a> {
+    Script(&'a String),
+    Group(&'a str, Vec<&'a String>),
+}
+
+/// Groups script names sharing a `:`, `.` or `/` separated prefix into nested sections, e.g.
+/// `build:web` and `build:ios` under `build`. A prefix only forms a group when more than one
+/// script shares it.
+///
+/// Groups are shown collapsed unless their path is in `expanded`, or `expand_all` is set.
+pub fn build_tree(
+    names: &[&String],
+    expanded: &HashSet<String>,
+    expand_all: bool,
+) -> Vec<TableRow> {
+    let mut rows = vec![];
+    push_level(names, 0, None, expanded, expand_all, &mut rows);
+
+    rows
+}
+
+/// Returns the name up to its `depth`th separator, if it has one.
+fn group_prefix(name: &str, depth: usize) -> Option<&str> {
+    name.match_indices(SEPARATORS)
+        .nth(depth)
+        .map(|(index, _)| &name[..index])
+}
+
+/// The last segment of a group path, shown as the group's label.
+pub fn group_label(path: &str) -> &str {
+    path.rsplit(SEPARATORS).next().unwrap_or(path)
+}
+
+fn push_level(
+    names: &[&String],
+    depth: usize,
+    group: Option<&str>,
+    expanded: &HashSet<String>,
+    expand_all: bool,
+    rows: &mut Vec<TableRow>,
+) {
+    let mut groups: BTreeMap<&str, Vec<&String>> = BTreeMap::new();
+    let mut entries = vec![];
+
+    for name in names {
+        match group_prefix(name, depth) {
+            Some(prefix) => groups.entry(prefix).or_default().push(*name),
+            None => entries.push(Entry::Script(name)),
+        }
+    }
+
+    for (prefix, members) in groups {
+        if members.len() == 1 {
+            entries.push(Entry::Script(members[0]));
+        } else {
+            entries.push(Entry::Group(prefix, members));
+        }
+    }
+
+    entries.sort_by_key(|entry| match entry {
+        Entry::Script(name) => (name.as_str(), false),
+        Entry::Group(prefix, _) => (*prefix, true),
+    });
+
+    for entry in entries {
+        match entry {
+            Entry::Script(name) => rows.push(TableRow::Script {
+                name: name.to_string(),
+                depth,
+                group: group.map(str::to_string),
+            }),
+            Entry::Group(prefix, members) => {
+                let is_expanded = expand_all || expanded.contains(prefix);
+
+                rows.push(TableRow::Group {
+                    path: prefix.to_string(),
+                    depth,
+                    size: members.len(),
+                    expanded: is_expanded,
+                });
+
+                if is_expanded {
+                    push_level(
+                        &members,
+                        depth + 1,
+                        Some(prefix),
+                        expanded,
+                        expand_all,
+                        rows,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn script(name: &str, depth: usize, group: Option<&str>) -> TableRow {
+        TableRow::Script {
+            name: name.to_string(),
+            depth,
+            group: group.map(str::to_string),
+        }
+    }
+
+    fn group(path: &str, depth: usize, size: usize, expanded: bool) -> TableRow {
+        TableRow::Group {
+            path: path.to_string(),
+            depth,
+            size,
+            expanded,
+        }
+    }
+
+    #[test]
+    fn it_build_tree_groups_by_prefix_collapsed() {
+        let names = names(&["build", "build:ios", "build:web", "lint", "test:unit"]);
+        let refs: Vec<&String> = names.iter().collect();
+
+        let rows = build_tree(&refs, &HashSet::new(), false);
+
+        assert_eq!(
+            rows,
+            vec![
+                script("build", 0, None),
+                group("build", 0, 2, false),
+                script("lint", 0, None),
+                script("test:unit", 0, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_build_tree_expands_nested_groups() {
+        let names = names(&["build:web.dev", "build:web.prod", "build/ios"]);
+        let refs: Vec<&String> = names.iter().collect();
+        let expanded = HashSet::from(["build".to_string(), "build:web".to_string()]);
+
+        let rows = build_tree(&refs, &expanded, false);
+
+        assert_eq!(
+            rows,
+            vec![
+                group("build", 0, 3, true),
+                script("build/ios", 1, Some("build")),
+                group("build:web", 1, 2, true),
+                script("build:web.dev", 2, Some("build:web")),
+                script("build:web.prod", 2, Some("build:web")),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_build_tree_expand_all_ignores_collapsed_state() {
+        let names = names(&["test:e2e", "test:unit"]);
+        let refs: Vec<&String> = names.iter().collect();
+
+        let rows = build_tree(&refs, &HashSet::new(), true);
+
+        assert_eq!(
+            rows,
+            vec![
+                group("test", 0, 2, true),
+                script("test:e2e", 1, Some("test")),
+                script("test:unit", 1, Some("test")),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_group_label_returns_last_segment() {
+        assert_eq!(group_label("build"), "build");
+        assert_eq!(group_label("build:web"), "web");
+    }
+}
@@ -0,0 +1,158 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Normal,
+    Insert,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VimCommand {
+    Next,
+    Previous,
+    First,
+    Last,
+    HalfPageDown,
+    HalfPageUp,
+    Insert,
+    Normal,
+    Quit,
+    Ignore,
+}
+
+/// Modal navigation: in normal mode letters move around the table instead of being typed, and
+/// `/` or `i` switch to insert mode to type in the search box until `esc`.
+pub struct Vim {
+    pub mode: Mode,
+    pending_g: bool,
+}
+
+impl Vim {
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Normal,
+            pending_g: false,
+        }
+    }
+
+    /// Interprets a key for the current mode, returning `None` for keys that should fall through
+    /// to the key map.
+    pub fn handle(&mut self, key: KeyEvent) -> Option<VimCommand> {
+        let pending_g = std::mem::take(&mut self.pending_g);
+
+        if self.mode == Mode::Insert {
+            return (key.code == KeyCode::Esc).then(|| {
+                self.mode = Mode::Normal;
+                VimCommand::Normal
+            });
+        }
+
+        let is_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if key.modifiers.contains(KeyModifiers::ALT) {
+            return None;
+        }
+
+        let command = match (key.code, is_ctrl) {
+            (KeyCode::Char('d'), true) => VimCommand::HalfPageDown,
+            (KeyCode::Char('u'), true) => VimCommand::HalfPageUp,
+            (_, true) => return None,
+            (KeyCode::Char('j'), _) => VimCommand::Next,
+            (KeyCode::Char('k'), _) => VimCommand::Previous,
+            (KeyCode::Char('g'), _) if pending_g => VimCommand::First,
+            (KeyCode::Char('g'), _) => {
+                self.pending_g = true;
+                VimCommand::Ignore
+            }
+            (KeyCode::Char('G'), _) => VimCommand::Last,
+            (KeyCode::Char('/' | 'i'), _) => {
+                self.mode = Mode::Insert;
+                VimCommand::Insert
+            }
+            (KeyCode::Char('q'), _) => VimCommand::Quit,
+            (KeyCode::Char(_), _) => VimCommand::Ignore,
+            _ => return None,
+        };
+
+        Some(command)
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self.mode {
+            Mode::Normal => "-- NORMAL --",
+            Mode::Insert => "-- INSERT --",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn it_normal_mode_maps_motions() {
+        let mut vim = Vim::new();
+
+        assert_eq!(vim.handle(key(KeyCode::Char('j'))), Some(VimCommand::Next));
+        assert_eq!(
+            vim.handle(key(KeyCode::Char('k'))),
+            Some(VimCommand::Previous)
+        );
+        assert_eq!(vim.handle(key(KeyCode::Char('G'))), Some(VimCommand::Last));
+        assert_eq!(vim.handle(ctrl('d')), Some(VimCommand::HalfPageDown));
+        assert_eq!(vim.handle(ctrl('u')), Some(VimCommand::HalfPageUp));
+        assert_eq!(
+            vim.handle(key(KeyCode::Char('x'))),
+            Some(VimCommand::Ignore)
+        );
+    }
+
+    #[test]
+    fn it_gg_goes_to_first() {
+        let mut vim = Vim::new();
+
+        assert_eq!(
+            vim.handle(key(KeyCode::Char('g'))),
+            Some(VimCommand::Ignore)
+        );
+        assert_eq!(vim.handle(key(KeyCode::Char('g'))), Some(VimCommand::First));
+
+        vim.handle(key(KeyCode::Char('g')));
+        vim.handle(key(KeyCode::Char('j')));
+        assert_eq!(
+            vim.handle(key(KeyCode::Char('g'))),
+            Some(VimCommand::Ignore)
+        );
+    }
+
+    #[test]
+    fn it_slash_enters_insert_mode_until_esc() {
+        let mut vim = Vim::new();
+
+        assert_eq!(
+            vim.handle(key(KeyCode::Char('/'))),
+            Some(VimCommand::Insert)
+        );
+        assert_eq!(vim.mode, Mode::Insert);
+        assert_eq!(vim.handle(key(KeyCode::Char('j'))), None);
+
+        assert_eq!(vim.handle(key(KeyCode::Esc)), Some(VimCommand::Normal));
+        assert_eq!(vim.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn it_leaves_other_keys_to_key_map() {
+        let mut vim = Vim::new();
+
+        assert_eq!(vim.handle(key(KeyCode::Enter)), None);
+        assert_eq!(vim.handle(key(KeyCode::Esc)), None);
+        assert_eq!(vim.handle(ctrl('t')), None);
+    }
+}